@@ -0,0 +1,231 @@
+use crate::grid::Point;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Characters used in a map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbols {
+    pub asteroid: char,
+    pub empty: char,
+    /// Asteroid chosen as the monitoring station
+    pub station: char,
+}
+
+impl Default for Symbols {
+    fn default() -> Self {
+        Symbols {
+            asteroid: '#',
+            empty: '.',
+            station: 'X',
+        }
+    }
+}
+
+/// Answers written below the map of an example, after an empty line:
+/// `best: 11,13`, `visible: 210` and `vaporized 200: 8,2`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub best: Option<Point>,
+    pub visible: Option<usize>,
+    pub vaporized: Vec<(usize, Point)>,
+}
+
+impl Expected {
+    pub fn is_empty(&self) -> bool {
+        self.best.is_none() && self.visible.is_none() && self.vaporized.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    UnknownSymbol {
+        symbol: char,
+        x: usize,
+        y: usize,
+    },
+    /// Row `y` is not as wide as the first one
+    RaggedRow {
+        y: usize,
+        width: usize,
+        expected: usize,
+    },
+    SeveralStations(Point, Point),
+    /// Line `line` of the file is not a valid annotation
+    BadAnnotation {
+        line: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::UnknownSymbol { symbol, x, y } => {
+                write!(f, "Unknown symbol '{}' at ({}, {})", symbol, x, y)
+            }
+            MapError::RaggedRow { y, width, expected } => {
+                write!(f, "Row {} is {} wide instead of {}", y, width, expected)
+            }
+            MapError::SeveralStations(a, b) => write!(
+                f,
+                "Several stations: ({}, {}) and ({}, {})",
+                a.x, a.y, b.x, b.y
+            ),
+            MapError::BadAnnotation { line, text } => {
+                write!(f, "Invalid annotation on line {}: {}", line, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+/// Asteroid field as drawn in the puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsteroidMap {
+    pub width: usize,
+    pub height: usize,
+    /// Including the station
+    pub asteroids: HashSet<Point>,
+    pub station: Option<Point>,
+    pub expected: Expected,
+}
+
+fn parse_point(text: &str) -> Option<Point> {
+    let mut coordinates = text.trim().split(',');
+    let x = coordinates.next()?.trim().parse().ok()?;
+    let y = coordinates.next()?.trim().parse().ok()?;
+    if coordinates.next().is_some() {
+        return None;
+    }
+    Some(Point::new(x, y))
+}
+
+fn parse_annotation(text: &str, expected: &mut Expected) -> Option<()> {
+    let mut parts = text.splitn(2, ':');
+    let key = parts.next()?.trim();
+    let value = parts.next()?;
+    match key {
+        "best" => expected.best = Some(parse_point(value)?),
+        "visible" => expected.visible = Some(value.trim().parse().ok()?),
+        _ if key.starts_with("vaporized ") => {
+            let rank = key["vaporized ".len()..].trim().parse().ok()?;
+            expected.vaporized.push((rank, parse_point(value)?));
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+impl AsteroidMap {
+    pub fn parse(input: &str) -> Result<Self, MapError> {
+        Self::parse_with(input, &Symbols::default())
+    }
+    pub fn parse_with(input: &str, symbols: &Symbols) -> Result<Self, MapError> {
+        let mut lines = input.lines().enumerate();
+        let mut width = None;
+        let mut height = 0;
+        let mut asteroids = HashSet::new();
+        let mut station = None;
+        for (y, line) in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            let mut row_width = 0;
+            for (x, symbol) in line.chars().enumerate() {
+                let point = Point::new(x as isize, y as isize);
+                match symbol {
+                    c if c == symbols.empty => (),
+                    c if c == symbols.asteroid => {
+                        asteroids.insert(point);
+                    }
+                    c if c == symbols.station => {
+                        if let Some(other) = station {
+                            return Err(MapError::SeveralStations(other, point));
+                        }
+                        station = Some(point);
+                        asteroids.insert(point);
+                    }
+                    symbol => return Err(MapError::UnknownSymbol { symbol, x, y }),
+                }
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(expected) if expected != row_width => {
+                    return Err(MapError::RaggedRow {
+                        y,
+                        width: row_width,
+                        expected,
+                    })
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        let mut expected = Expected::default();
+        for (i, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+            if parse_annotation(line, &mut expected).is_none() {
+                return Err(MapError::BadAnnotation {
+                    line: i + 1,
+                    text: line.to_owned(),
+                });
+            }
+        }
+        Ok(AsteroidMap {
+            width: width.unwrap_or(0),
+            height,
+            asteroids,
+            station,
+            expected,
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        self.to_text_with(&Symbols::default())
+    }
+    pub fn to_text_with(&self, symbols: &Symbols) -> String {
+        let mut text = String::new();
+        for y in 0..self.height as isize {
+            for x in 0..self.width as isize {
+                let point = Point::new(x, y);
+                text.push(if self.station == Some(point) {
+                    symbols.station
+                } else if self.asteroids.contains(&point) {
+                    symbols.asteroid
+                } else {
+                    symbols.empty
+                });
+            }
+            text.push('\n');
+        }
+        if !self.expected.is_empty() {
+            text.push('\n');
+            if let Some(best) = self.expected.best {
+                text.push_str(&format!("best: {},{}\n", best.x, best.y));
+            }
+            if let Some(visible) = self.expected.visible {
+                text.push_str(&format!("visible: {}\n", visible));
+            }
+            for (rank, p) in &self.expected.vaporized {
+                text.push_str(&format!("vaporized {}: {},{}\n", rank, p.x, p.y));
+            }
+        }
+        text
+    }
+}
+
+impl fmt::Display for AsteroidMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_text())
+    }
+}
+
+impl std::str::FromStr for AsteroidMap {
+    type Err = MapError;
+
+    fn from_str(input: &str) -> Result<Self, MapError> {
+        Self::parse(input)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::grid::Point;
+use num::integer::gcd;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+mod map;
+mod report;
+mod visibility;
+pub use map::{AsteroidMap, Expected, MapError, Symbols};
+pub use report::VisibilityReport;
+pub use visibility::Visibility;
+
+#[aoc_generator(day10)]
+pub fn get_asteroids(input: &str) -> Result<HashSet<Point>, MapError> {
+    Ok(AsteroidMap::parse(input)?.asteroids)
+}
+/// Direction from an asteroid to another, reduced so that asteroids hiding each other share it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sight {
+    dx: isize,
+    dy: isize,
+}
+
+/// Way the laser turns, as seen on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Sight {
+    pub const UP: Sight = Sight { dx: 0, dy: -1 };
+
+    /// `None` for the null vector, which has no direction
+    pub fn new(dx: isize, dy: isize) -> Option<Self> {
+        if dx == 0 && dy == 0 {
+            return None;
+        }
+        let divisor = gcd(dx, dy);
+        Some(Sight {
+            dx: dx / divisor,
+            dy: dy / divisor,
+        })
+    }
+    fn between(from: Point, to: Point) -> Self {
+        Sight::new(to.x - from.x, to.y - from.y).expect("no direction from a point to itself")
+    }
+    fn cross(&self, other: &Self) -> isize {
+        self.dx * other.dy - self.dy * other.dx
+    }
+    fn dot(&self, other: &Self) -> isize {
+        self.dx * other.dx + self.dy * other.dy
+    }
+    /// Order in which a laser pointing at `start` and turning in `rotation` goes over sights
+    pub fn sweep_cmp(&self, other: &Self, start: Sight, rotation: Rotation) -> Ordering {
+        let sign = match rotation {
+            Rotation::Clockwise => 1,
+            Rotation::CounterClockwise => -1,
+        };
+        // 0 from `start` to just before the opposite direction, 1 for the other half turn
+        let half = |sight: &Sight| {
+            let cross = sign * start.cross(sight);
+            if cross > 0 || (cross == 0 && start.dot(sight) > 0) {
+                0
+            } else {
+                1
+            }
+        };
+        half(self)
+            .cmp(&half(other))
+            .then_with(|| 0.cmp(&(sign * self.cross(other))))
+    }
+}
+
+/// Clockwise order on screen, starting straight up
+impl Ord for Sight {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sweep_cmp(other, Sight::UP, Rotation::Clockwise)
+    }
+}
+impl PartialOrd for Sight {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Number of other asteroids seen from `point`, one per direction
+fn count_in_view_from(point: Point, asteroids: &HashSet<Point>) -> usize {
+    asteroids
+        .iter()
+        .filter(|a| **a != point)
+        .map(|a| Sight::between(point, *a))
+        .collect::<HashSet<_>>()
+        .len()
+}
+/// Other asteroids grouped by the direction they are seen in from `point`, in no particular order
+fn find_asteroid_in_view_from(
+    point: Point,
+    asteroids: &HashSet<Point>,
+) -> HashMap<Sight, Vec<Point>> {
+    let mut sights = HashMap::new();
+    for asteroid in asteroids.iter().filter(|a| **a != point) {
+        sights
+            .entry(Sight::between(point, *asteroid))
+            .or_insert_with(Vec::new)
+            .push(*asteroid);
+    }
+    sights
+}
+fn find_most_beacony(asteroids: &HashSet<Point>) -> (usize, Point) {
+    asteroids
+        .iter()
+        .map(|source| (count_in_view_from(*source, asteroids), *source))
+        .max_by(|(len1, _), (len2, _)| len1.cmp(len2))
+        .unwrap()
+}
+#[aoc(day10, part1)]
+pub fn find_beacon_asteroid(input: &HashSet<Point>) -> usize {
+    find_most_beacony(input).0
+}
+#[aoc(day10, part1, report)]
+pub fn visibility_report(input: &HashSet<Point>) -> Result<usize, String> {
+    let (_, count) = VisibilityReport::new(input)
+        .best()
+        .ok_or_else(|| "No asteroids".to_owned())?;
+    Ok(count)
+}
+
+/// Asteroids in the order the laser vaporizes them, closest first in each direction
+#[derive(Debug, Clone)]
+pub struct Vaporization {
+    // farthest first so that the closest one is popped
+    lines: Vec<Vec<Point>>,
+    next_line: usize,
+    remaining: usize,
+}
+
+impl Iterator for Vaporization {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            let line = self.next_line;
+            self.next_line = (line + 1) % self.lines.len();
+            if let Some(asteroid) = self.lines[line].pop() {
+                self.remaining -= 1;
+                return Some(asteroid);
+            }
+        }
+    }
+}
+
+/// Every other asteroid, as vaporized by a laser at `station` first pointing at `start`
+pub fn vaporization_order(
+    station: Point,
+    asteroids: &HashSet<Point>,
+    rotation: Rotation,
+    start: Sight,
+) -> Vaporization {
+    let mut sights: Vec<_> = find_asteroid_in_view_from(station, asteroids)
+        .into_iter()
+        .collect();
+    sights.sort_unstable_by(|(a, _), (b, _)| a.sweep_cmp(b, start, rotation));
+    let lines: Vec<_> = sights
+        .into_iter()
+        .map(|(_, mut line)| {
+            line.sort_unstable_by_key(|a| std::cmp::Reverse(a.distance_squared(&station)));
+            line
+        })
+        .collect();
+    Vaporization {
+        remaining: lines.iter().map(Vec::len).sum(),
+        lines,
+        next_line: 0,
+    }
+}
+
+#[aoc(day10, part2)]
+pub fn find_lasered(input: &HashSet<Point>) -> Result<isize, String> {
+    let (_, station) = find_most_beacony(input);
+    let point = vaporization_order(station, input, Rotation::Clockwise, Sight::UP)
+        .nth(199)
+        .ok_or_else(|| "Fewer than 200 asteroids to vaporize".to_owned())?;
+    Ok(100 * point.x + point.y)
+}
@@ -0,0 +1,143 @@
+use super::{count_in_view_from, find_asteroid_in_view_from};
+use crate::bitmap::{Raster, Rgb, BLACK, DARK_GRAY, WHITE};
+use crate::grid::{BoundingBox, Point, SparseGrid};
+use crate::render::{Cell, Renderer};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use yansi::Color;
+
+/// How many asteroids each asteroid of the field can see
+#[derive(Debug, Clone)]
+pub struct VisibilityReport {
+    asteroids: HashSet<Point>,
+    counts: HashMap<Point, usize>,
+}
+
+// Blue for the asteroids seeing the fewest others, red for the ones seeing the most
+fn heat(count: usize, max: usize) -> Rgb {
+    let hot = (255 * count / max.max(1)) as u8;
+    [hot, 40, 255 - hot]
+}
+
+impl VisibilityReport {
+    pub fn new(asteroids: &HashSet<Point>) -> Self {
+        let counts = asteroids
+            .iter()
+            .map(|&asteroid| (asteroid, count_in_view_from(asteroid, asteroids)))
+            .collect();
+        VisibilityReport {
+            asteroids: asteroids.clone(),
+            counts,
+        }
+    }
+    pub fn count(&self, asteroid: &Point) -> Option<usize> {
+        self.counts.get(asteroid).copied()
+    }
+    /// The `n` asteroids seeing the most others, best first
+    pub fn top(&self, n: usize) -> Vec<(Point, usize)> {
+        let mut counts: Vec<_> = self.counts.iter().map(|(p, c)| (*p, *c)).collect();
+        counts.sort_unstable_by_key(|&(p, count)| (std::cmp::Reverse(count), p.y, p.x));
+        counts.truncate(n);
+        counts
+    }
+    pub fn best(&self) -> Option<(Point, usize)> {
+        self.top(1).pop()
+    }
+
+    /// Asteroids seen from `station`, each with the empty points in between
+    pub fn lines_of_sight(&self, station: Point) -> Vec<(Point, Vec<Point>)> {
+        find_asteroid_in_view_from(station, &self.asteroids)
+            .into_iter()
+            .map(|(sight, line)| {
+                let target = *line
+                    .iter()
+                    .min_by_key(|a| a.distance_squared(&station))
+                    .unwrap();
+                let between = (1..)
+                    .map(|i| Point::new(station.x + i * sight.dx, station.y + i * sight.dy))
+                    .take_while(|p| *p != target)
+                    .collect();
+                (target, between)
+            })
+            .collect()
+    }
+
+    fn colors(&self, station: Option<Point>) -> HashMap<Point, Rgb> {
+        let max = self.counts.values().copied().max().unwrap_or(0);
+        let mut colors: HashMap<_, _> = self
+            .counts
+            .iter()
+            .map(|(p, count)| (*p, heat(*count, max)))
+            .collect();
+        if let Some(station) = station {
+            for (_, between) in self.lines_of_sight(station) {
+                colors.extend(between.iter().map(|p| (*p, DARK_GRAY)));
+            }
+            colors.insert(station, WHITE);
+        }
+        colors
+    }
+
+    /// Heatmap of the field, with the lines of sight of `station` if there is one
+    pub fn render(&self, station: Option<Point>) -> String {
+        let max = self.counts.values().copied().max().unwrap_or(0);
+        // plain text shows counts from 0 to 9, 9 being the best one
+        let digit = |count: usize| std::char::from_digit((9 * count / max.max(1)) as u32, 10);
+        let mut cells: SparseGrid<Cell> = self
+            .counts
+            .iter()
+            .map(|(p, count)| {
+                let [r, g, b] = heat(*count, max);
+                let cell = Cell::new('#')
+                    .plain(digit(*count).unwrap())
+                    .fg(Color::RGB(r, g, b));
+                (*p, cell)
+            })
+            .collect();
+        let mut renderer = Renderer::new().background(Cell::new(' ').plain('.'));
+        if let Some(station) = station {
+            let sight = Cell::new('·').plain('+');
+            for (target, between) in self.lines_of_sight(station) {
+                for p in between {
+                    cells.insert(p, sight);
+                }
+                if let Some(cell) = cells.get(&target).copied() {
+                    cells.insert(target, cell.bold().underline());
+                }
+            }
+            let station_cell = Cell::new('S').bold().bg(Color::White).fg(Color::Black);
+            renderer = renderer
+                .highlight(station, station_cell)
+                .legend(station_cell, "station")
+                .legend(sight, "line of sight");
+        }
+        renderer.render(&cells)
+    }
+    /// Heatmap as a picture, with the lines of sight of `station` if there is one
+    pub fn raster(&self, station: Option<Point>) -> Raster {
+        let colors = self.colors(station);
+        let bounds = match BoundingBox::from_points(colors.keys()) {
+            Some(bounds) => bounds,
+            None => return Raster::new(0, 0, BLACK),
+        };
+        let (width, height) = (bounds.width() as usize, bounds.height() as usize);
+        let mut raster = Raster::new(width, height, BLACK);
+        for (p, color) in colors {
+            raster.set(
+                (p.x - bounds.min.x) as usize,
+                (p.y - bounds.min.y) as usize,
+                color,
+            );
+        }
+        raster
+    }
+    /// Heatmap as a binary PPM, each point being a `scale`x`scale` square
+    pub fn write_ppm<W: Write>(
+        &self,
+        out: &mut W,
+        scale: usize,
+        station: Option<Point>,
+    ) -> io::Result<()> {
+        self.raster(station).write_ppm(out, scale)
+    }
+}
@@ -0,0 +1,69 @@
+use super::{find_asteroid_in_view_from, Sight};
+use crate::grid::Point;
+use std::collections::{HashMap, HashSet};
+
+/// What every asteroid sees, kept up to date as asteroids are removed from the field
+#[derive(Debug, Clone)]
+pub struct Visibility {
+    // other asteroids grouped by direction for each asteroid
+    stations: HashMap<Point, HashMap<Sight, Vec<Point>>>,
+}
+
+impl Visibility {
+    pub fn new(asteroids: &HashSet<Point>) -> Self {
+        let stations = asteroids
+            .iter()
+            .map(|&station| (station, find_asteroid_in_view_from(station, asteroids)))
+            .collect();
+        Visibility { stations }
+    }
+    pub fn len(&self) -> usize {
+        self.stations.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.stations.is_empty()
+    }
+    pub fn contains(&self, asteroid: &Point) -> bool {
+        self.stations.contains_key(asteroid)
+    }
+    /// Number of asteroids seen from `station`, `None` if there is no asteroid there
+    pub fn visible_count(&self, station: &Point) -> Option<usize> {
+        self.stations.get(station).map(|sights| sights.len())
+    }
+    /// Closest asteroid in each direction from `station`
+    pub fn visible_from(&self, station: &Point) -> Vec<Point> {
+        self.stations
+            .get(station)
+            .map(|sights| {
+                sights
+                    .values()
+                    .filter_map(|line| line.iter().min_by_key(|a| a.distance_squared(station)))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    pub fn best(&self) -> Option<(Point, usize)> {
+        self.stations
+            .iter()
+            .map(|(p, sights)| (*p, sights.len()))
+            .max_by_key(|&(p, count)| (count, std::cmp::Reverse((p.y, p.x))))
+    }
+
+    /// Removes the asteroid and updates what the others see, only looking at the line it was on
+    /// from each of them. Returns `false` if there was no asteroid there.
+    pub fn remove(&mut self, asteroid: &Point) -> bool {
+        if self.stations.remove(asteroid).is_none() {
+            return false;
+        }
+        for (station, sights) in &mut self.stations {
+            let sight = Sight::between(*station, *asteroid);
+            let line = sights.get_mut(&sight).unwrap();
+            line.retain(|p| p != asteroid);
+            if line.is_empty() {
+                sights.remove(&sight);
+            }
+        }
+        true
+    }
+}
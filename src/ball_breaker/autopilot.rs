@@ -0,0 +1,122 @@
+use super::{move_towards, ArcadeError, Controller, Game, Greedy, Point, Tile};
+use crate::intcode::{parse_intcode, IntcodeComputer};
+use std::collections::HashSet;
+
+// Bounds the simulation in case the ball gets stuck bouncing between blocks
+const MAX_PREDICTION_STEPS: usize = 10_000;
+
+/// Moves the paddle only towards the column where the ball is predicted to reach the paddle row
+#[derive(Debug, Default)]
+pub struct Predictive {
+    last_ball: Option<Point>,
+}
+
+impl Predictive {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Controller for Predictive {
+    fn joystick(&mut self, game: &Game) -> i64 {
+        let ball = game.ball();
+        let target = match self.last_ball {
+            Some(last) if last != ball => predict_landing(game, last, ball).unwrap_or(ball.x),
+            _ => ball.x,
+        };
+        self.last_ball = Some(ball);
+        move_towards(game.paddle().x, target)
+    }
+}
+
+fn signum(from: usize, to: usize) -> isize {
+    move_towards(from, to) as isize
+}
+
+// Follows the ball from its current velocity, bouncing on walls and (a copy of) the blocks,
+// until it comes down to the row just above the paddle
+fn predict_landing(game: &Game, last: Point, ball: Point) -> Option<usize> {
+    let mut dx = signum(last.x, ball.x);
+    let mut dy = signum(last.y, ball.y);
+    if dx == 0 || dy == 0 {
+        return None;
+    }
+    let walls: HashSet<(isize, isize)> = game
+        .screen()
+        .iter()
+        .filter(|(_, t)| **t == Tile::Wall)
+        .map(|(p, _)| (p.x as isize, p.y as isize))
+        .collect();
+    let mut blocks: HashSet<(isize, isize)> = game
+        .screen()
+        .iter()
+        .filter(|(_, t)| **t == Tile::Block)
+        .map(|(p, _)| (p.x as isize, p.y as isize))
+        .collect();
+    let mut hit = |cell: (isize, isize)| walls.contains(&cell) || blocks.remove(&cell);
+
+    let landing_row = game.paddle().y as isize - 1;
+    let (mut x, mut y) = (ball.x as isize, ball.y as isize);
+    for _ in 0..MAX_PREDICTION_STEPS {
+        if y > landing_row {
+            return None;
+        }
+        if dy > 0 && y == landing_row {
+            return Some(x as usize);
+        }
+        let mut bounced = false;
+        if hit((x + dx, y)) {
+            dx = -dx;
+            bounced = true;
+        }
+        if hit((x, y + dy)) {
+            dy = -dy;
+            bounced = true;
+        }
+        if !bounced && hit((x + dx, y + dy)) {
+            dx = -dx;
+            dy = -dy;
+            bounced = true;
+        }
+        if !bounced {
+            x += dx;
+            y += dy;
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub score: usize,
+    pub frames: usize,
+    pub joystick_moves: usize,
+    pub blocks_left: usize,
+}
+
+pub fn run_with<C: Controller>(code: &[i64], controller: &mut C) -> Result<Run, ArcadeError> {
+    let mut computer = IntcodeComputer::new(Vec::from(code));
+    computer.arbitrary_set(0, 2);
+    let mut game = Game::new(computer);
+    game.play(controller)?;
+
+    Ok(Run {
+        score: game.score(),
+        frames: game.inputs(),
+        joystick_moves: game.joystick_moves(),
+        blocks_left: game.blocks_left(),
+    })
+}
+
+/// Plays the game with the greedy and the predictive controllers, in that order
+pub fn compare_controllers(code: &[i64]) -> Result<(Run, Run), ArcadeError> {
+    Ok((
+        run_with(code, &mut Greedy)?,
+        run_with(code, &mut Predictive::new())?,
+    ))
+}
+
+#[aoc(day13, part2, predictive)]
+pub fn play_the_game_predictive(code: &[i64]) -> Result<usize, ArcadeError> {
+    Ok(run_with(code, &mut Predictive::new())?.score)
+}
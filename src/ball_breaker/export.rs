@@ -0,0 +1,101 @@
+use super::{Frame, Point, Tile};
+use crate::bitmap::{upscale, Raster, Rgb, BLACK, BLUE, GRAY, ORANGE, WHITE};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const PALETTE: [Rgb; 5] = [BLACK, GRAY, ORANGE, BLUE, WHITE];
+
+fn palette_index(tile: Tile) -> u8 {
+    match tile {
+        Tile::Empty => 0,
+        Tile::Wall => 1,
+        Tile::Block => 2,
+        Tile::Paddle => 3,
+        Tile::Ball => 4,
+    }
+}
+
+/// Size of the screen, in tiles, needed to show all the frames
+pub fn screen_size(frames: &[Frame]) -> (usize, usize) {
+    frames
+        .iter()
+        .flat_map(|frame| {
+            frame
+                .screen
+                .keys()
+                .chain(std::iter::once(&frame.ball))
+                .chain(std::iter::once(&frame.paddle))
+        })
+        .fold((0, 0), |(width, height), p| {
+            (width.max(p.x + 1), height.max(p.y + 1))
+        })
+}
+
+// Palette index of each tile, row by row
+fn palette_indices(frame: &Frame, (width, height): (usize, usize)) -> Vec<u8> {
+    let mut pixels = vec![0; width * height];
+    let tiles = frame
+        .screen
+        .iter()
+        .map(|(p, t)| (*p, *t))
+        .chain(vec![(frame.paddle, Tile::Paddle), (frame.ball, Tile::Ball)]);
+    for (Point { x, y }, tile) in tiles {
+        if x < width && y < height {
+            pixels[y * width + x] = palette_index(tile);
+        }
+    }
+    pixels
+}
+
+/// A single frame as a picture of `size` tiles
+pub fn raster(frame: &Frame, size: (usize, usize)) -> Raster {
+    let mut raster = Raster::new(size.0, size.1, BLACK);
+    for (i, &index) in palette_indices(frame, size).iter().enumerate() {
+        raster.set(i % size.0, i / size.0, PALETTE[index as usize]);
+    }
+    raster
+}
+
+/// Writes a single frame as a binary PPM, each tile being a `scale`x`scale` square
+pub fn write_ppm<W: Write>(
+    frame: &Frame,
+    size: (usize, usize),
+    scale: usize,
+    out: &mut W,
+) -> io::Result<()> {
+    raster(frame, size).write_ppm(out, scale)
+}
+
+/// Writes every frame as `frame_<n>.ppm` in `directory`
+pub fn export_ppm(frames: &[Frame], directory: &Path, scale: usize) -> io::Result<()> {
+    let size = screen_size(frames);
+    for (i, frame) in frames.iter().enumerate() {
+        let file = File::create(directory.join(format!("frame_{:05}.ppm", i)))?;
+        let mut out = BufWriter::new(file);
+        write_ppm(frame, size, scale, &mut out)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Writes the frames as an animated GIF looping forever, `delay` is in hundredths of a second
+pub fn export_gif<W: Write>(frames: &[Frame], out: W, scale: usize, delay: u16) -> io::Result<()> {
+    use gif::SetParameter;
+
+    let size = screen_size(frames);
+    let too_big = |_| io::Error::new(io::ErrorKind::InvalidInput, "screen too big for a gif");
+    let width = u16::try_from(size.0 * scale).map_err(too_big)?;
+    let height = u16::try_from(size.1 * scale).map_err(too_big)?;
+    let palette: Vec<u8> = PALETTE.iter().flat_map(|c| c.iter().copied()).collect();
+    let mut encoder = gif::Encoder::new(out, width, height, &palette)?;
+    encoder.set(gif::Repeat::Infinite)?;
+    for frame in frames {
+        let pixels = upscale(palette_indices(frame, size).chunks(size.0.max(1)), scale);
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, &pixels, None);
+        gif_frame.delay = delay;
+        encoder.write_frame(&gif_frame)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,291 @@
+use crate::intcode::{IntcodeComputer, IntcodeState, parse_intcode};
+use std::collections::HashMap;
+
+pub mod autopilot;
+pub mod export;
+pub mod patcher;
+
+/// The screen starts at the top left corner, so coordinates are never negative
+pub type Point = crate::grid::Point<usize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArcadeError {
+    UnknownTile(i64),
+    NegativeCoordinates { x: i64, y: i64 },
+    /// The program stopped or asked for input after only `received` values of a triple
+    TruncatedOutput { received: usize },
+}
+
+impl std::fmt::Display for ArcadeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ArcadeError::UnknownTile(i) => write!(f, "Unknown tile id: {}", i),
+            ArcadeError::NegativeCoordinates { x, y } => {
+                write!(f, "Negative coordinates: ({}, {})", x, y)
+            }
+            ArcadeError::TruncatedOutput { received } => {
+                write!(f, "Incomplete output, got {} values out of 3", received)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArcadeError {}
+
+impl Tile {
+    fn from_int(value: i64) -> Result<Tile, ArcadeError> {
+        match value {
+            0 => Ok(Tile::Empty),
+            1 => Ok(Tile::Wall),
+            2 => Ok(Tile::Block),
+            3 => Ok(Tile::Paddle),
+            4 => Ok(Tile::Ball),
+            i => Err(ArcadeError::UnknownTile(i)),
+        }
+    }
+    fn to_int(self) -> i64 {
+        match self {
+            Tile::Empty => 0,
+            Tile::Wall => 1,
+            Tile::Block => 2,
+            Tile::Paddle => 3,
+            Tile::Ball => 4,
+        }
+    }
+}
+
+/// Snapshot of everything displayed by the arcade at a given time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub screen: HashMap<Point, Tile>,
+    pub ball: Point,
+    pub paddle: Point,
+    pub score: usize,
+}
+
+pub struct Game {
+    screen: std::collections::HashMap<Point, Tile>,
+    paddle: Point,
+    score: usize,
+    ball: Point,
+    computer: IntcodeComputer,
+
+    recording: bool,
+    frames: Vec<Frame>,
+
+    inputs: usize,
+    joystick_moves: usize,
+}
+
+/// Decides where the joystick is pushed each time the game asks for input
+pub trait Controller {
+    /// Returns -1 for left, 0 for neutral and 1 for right
+    fn joystick(&mut self, game: &Game) -> i64;
+}
+
+/// Always moves the paddle towards the current position of the ball
+pub struct Greedy;
+
+impl Controller for Greedy {
+    fn joystick(&mut self, game: &Game) -> i64 {
+        move_towards(game.paddle.x, game.ball.x)
+    }
+}
+
+fn move_towards(from: usize, to: usize) -> i64 {
+    match to.cmp(&from) {
+        std::cmp::Ordering::Less => -1,
+        std::cmp::Ordering::Equal => 0,
+        std::cmp::Ordering::Greater => 1,
+    }
+}
+
+enum Output {
+    Value(i64),
+    Input,
+    Finished,
+}
+
+enum Update {
+    Draw(Point, Tile),
+    Score(usize),
+    Input,
+}
+
+impl Game {
+    pub fn new(computer: IntcodeComputer) -> Self {
+        Self {
+            computer,
+            ball: Point{x: 0, y: 0},
+            paddle: Point{x: 0, y: 0},
+            score: 0,
+            screen: HashMap::new(),
+
+            recording: false,
+            frames: Vec::new(),
+
+            inputs: 0,
+            joystick_moves: 0,
+        }
+    }
+    /// Same as `new`, but keeps a frame each time the game asks for input and once it is over
+    pub fn recording(computer: IntcodeComputer) -> Self {
+        let mut game = Self::new(computer);
+        game.recording = true;
+        game
+    }
+    pub fn frame(&self) -> Frame {
+        Frame {
+            screen: self.screen.clone(),
+            ball: self.ball,
+            paddle: self.paddle,
+            score: self.score,
+        }
+    }
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+    pub fn score(&self) -> usize {
+        self.score
+    }
+    pub fn ball(&self) -> Point {
+        self.ball
+    }
+    pub fn paddle(&self) -> Point {
+        self.paddle
+    }
+    pub fn screen(&self) -> &HashMap<Point, Tile> {
+        &self.screen
+    }
+    pub fn blocks_left(&self) -> usize {
+        self.screen.values().filter(|t| **t == Tile::Block).count()
+    }
+    /// Number of times the game asked for input
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+    /// Number of inputs where the joystick was not neutral
+    pub fn joystick_moves(&self) -> usize {
+        self.joystick_moves
+    }
+    fn next_output(&mut self) -> Output {
+        loop {
+            match self.computer.step() {
+                IntcodeState::Ready => continue,
+                IntcodeState::NeedsInput => return Output::Input,
+                IntcodeState::Finished => return Output::Finished,
+                IntcodeState::Outputed => {
+                    return Output::Value(*self.computer.output().last().unwrap())
+                }
+            }
+        }
+    }
+    fn read_input(&mut self) -> Result<Option<Update>, ArcadeError> {
+        let mut triple = [0; 3];
+        for (received, value) in triple.iter_mut().enumerate() {
+            *value = match self.next_output() {
+                Output::Value(value) => value,
+                Output::Input if received == 0 => return Ok(Some(Update::Input)),
+                Output::Finished if received == 0 => return Ok(None),
+                _ => return Err(ArcadeError::TruncatedOutput { received }),
+            };
+        }
+        let [x, y, tile] = triple;
+        if x == -1 && y == 0 {
+            Ok(Some(Update::Score(tile as usize)))
+        } else if x < 0 || y < 0 {
+            Err(ArcadeError::NegativeCoordinates { x, y })
+        } else {
+            let point = Point { x: x as usize, y: y as usize };
+            Ok(Some(Update::Draw(point, Tile::from_int(tile)?)))
+        }
+    }
+    pub fn execute(&mut self) -> Result<(), ArcadeError> {
+        self.play(&mut Greedy)
+    }
+    pub fn play<C: Controller>(&mut self, controller: &mut C) -> Result<(), ArcadeError> {
+        while self.advance(controller)? {}
+        Ok(())
+    }
+    /// Runs the game until it asks for input and feeds it, returns false once the game is over
+    pub fn advance<C: Controller>(&mut self, controller: &mut C) -> Result<bool, ArcadeError> {
+        loop {
+            match self.read_input()? {
+                Some(Update::Draw(p, Tile::Empty)) => {
+                    self.screen.remove(&p);
+                }
+                Some(Update::Draw(p, Tile::Ball)) => {
+                    self.ball = p;
+                }
+                Some(Update::Draw(p, Tile::Paddle)) => {
+                    self.paddle = p;
+                }
+                Some(Update::Draw(p, t)) => {
+                    self.screen.insert(p, t);
+                }
+                Some(Update::Score(i)) => {
+                    self.score = i;
+                }
+                Some(Update::Input) => {
+                    if self.recording {
+                        self.frames.push(self.frame());
+                    }
+                    self.input_joystick(controller);
+                    return Ok(true);
+                }
+                None => {
+                    if self.recording {
+                        self.frames.push(self.frame());
+                    }
+                    return Ok(false);
+                }
+            }
+        }
+    }
+    fn input_joystick<C: Controller>(&mut self, controller: &mut C) {
+        let joystick_direction = controller.joystick(self);
+        self.inputs += 1;
+        if joystick_direction != 0 {
+            self.joystick_moves += 1;
+        }
+        self.computer.add_input(joystick_direction);
+    }
+}
+
+#[aoc(day13, part1)]
+pub fn count_tiles(code: &[i64]) -> Result<usize, ArcadeError> {
+    let computer = IntcodeComputer::new(Vec::from(code));
+    let mut game = Game::new(computer);
+    game.execute()?;
+
+    Ok(game.blocks_left())
+}
+
+#[aoc(day13, part2)]
+pub fn play_the_game(code: &[i64]) -> Result<usize, ArcadeError> {
+    let mut computer = IntcodeComputer::new(Vec::from(code));
+    computer.arbitrary_set(0, 2);
+    let mut game = Game::new(computer);
+    game.execute()?;
+
+    Ok(game.score)
+}
+
+/// Plays the whole game, returning every frame shown to the player
+pub fn record_game(code: &[i64]) -> Result<Vec<Frame>, ArcadeError> {
+    let mut computer = IntcodeComputer::new(Vec::from(code));
+    computer.arbitrary_set(0, 2);
+    let mut game = Game::recording(computer);
+    game.execute()?;
+
+    Ok(game.frames)
+}
@@ -0,0 +1,368 @@
+use super::{ArcadeError, Controller, Game, Point, Tile};
+use crate::intcode::{parse_intcode, IntcodeComputer};
+
+/// Where the arcade keeps its state in the Intcode memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    /// Address of the tile at (0, 0), tiles are stored row by row
+    pub grid: usize,
+    pub width: usize,
+    pub height: usize,
+    pub paddle_row: usize,
+    /// Address of the score table, which has one entry per tile and follows the grid
+    pub score_table: usize,
+}
+
+impl Layout {
+    fn cells(&self) -> usize {
+        self.width * self.height
+    }
+    pub fn tile_address(&self, point: Point) -> usize {
+        self.grid + point.y * self.width + point.x
+    }
+    pub fn tile(&self, computer: &IntcodeComputer, point: Point) -> Result<Tile, ArcadeError> {
+        Tile::from_int(computer.memory()[self.tile_address(point)])
+    }
+    pub fn set_tile(&self, computer: &mut IntcodeComputer, point: Point, tile: Tile) {
+        computer.arbitrary_set(self.tile_address(point), tile.to_int());
+    }
+    /// Replaces the whole paddle row between the walls with paddle, so the ball can't be missed
+    pub fn widen_paddle(&self, computer: &mut IntcodeComputer) {
+        for x in 1..(self.width - 1) {
+            self.set_tile(
+                computer,
+                Point {
+                    x,
+                    y: self.paddle_row,
+                },
+                Tile::Paddle,
+            );
+        }
+    }
+    pub fn score_table<'a>(&self, computer: &'a IntcodeComputer) -> &'a [i64] {
+        &computer.memory()[self.score_table..(self.score_table + self.cells())]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchError {
+    Arcade(ArcadeError),
+    /// The game ended before asking for input, so there was no screen to look for
+    EndedEarly,
+    UnexpectedScreen {
+        width: usize,
+        height: usize,
+    },
+    GridNotFound,
+    /// The score table would end at `needed` but the memory only has `available` values
+    MemoryTooShort {
+        needed: usize,
+        available: usize,
+    },
+    NoMatchingHash,
+}
+
+impl std::fmt::Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PatchError::Arcade(e) => write!(f, "{}", e),
+            PatchError::EndedEarly => write!(f, "Game ended before asking for input"),
+            PatchError::UnexpectedScreen { width, height } => {
+                write!(f, "Unexpected screen of {}x{}", width, height)
+            }
+            PatchError::GridNotFound => write!(f, "Tile grid not found in memory"),
+            PatchError::MemoryTooShort { needed, available } => write!(
+                f,
+                "Memory too short to hold the score table: {} values instead of {}",
+                available, needed
+            ),
+            PatchError::NoMatchingHash => write!(f, "Score hash not found in the code"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl From<ArcadeError> for PatchError {
+    fn from(e: ArcadeError) -> Self {
+        PatchError::Arcade(e)
+    }
+}
+
+/// How a block's position is numbered before being hashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellOrder {
+    /// `y * width + x`, the order of the tile grid
+    RowMajor,
+    /// `x * height + y`
+    ColumnMajor,
+}
+
+/// Index in the score table of the points given by a block.
+///
+/// The table is not laid out like the grid: when a block breaks, the arcade numbers its cell,
+/// hashes it as `(multiplier * cell + offset) % cells` and adds the table entry at that index.
+/// The constants are compiled into the program, see `from_code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoreHash {
+    pub order: CellOrder,
+    pub multiplier: usize,
+    pub offset: usize,
+}
+
+const ADD: i64 = 1;
+const MUL: i64 = 2;
+// Most instructions between the numbering of the cell and the modulo
+const HASH_WINDOW: usize = 12;
+
+/// Opcode and immediate input parameters of an instruction
+#[derive(Debug, Clone, Copy)]
+struct Instruction {
+    opcode: i64,
+    immediates: [Option<i64>; 2],
+}
+
+impl Instruction {
+    fn immediate(&self) -> Option<i64> {
+        self.immediates.iter().flatten().copied().next()
+    }
+    fn uses(&self, value: i64) -> bool {
+        self.immediates.contains(&Some(value))
+    }
+}
+
+// Reads the code as instructions, skipping single values that can't be one
+fn decode(code: &[i64]) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut address = 0;
+    while address < code.len() {
+        let value = code[address];
+        let parameters = match value % 100 {
+            1 | 2 | 7 | 8 => 3,
+            3 | 4 | 9 => 1,
+            5 | 6 => 2,
+            99 => 0,
+            _ => {
+                address += 1;
+                continue;
+            }
+        };
+        let mode = |i: u32| value / 10i64.pow(i + 2) % 10;
+        if value < 0 || (0..parameters).any(|i| mode(i) > 2) || value >= 10i64.pow(parameters + 2) {
+            address += 1;
+            continue;
+        }
+        let mut immediates = [None; 2];
+        for (i, immediate) in immediates.iter_mut().enumerate() {
+            if (i as u32) < parameters && mode(i as u32) == 1 {
+                *immediate = code.get(address + 1 + i).copied();
+            }
+        }
+        instructions.push(Instruction {
+            opcode: value % 100,
+            immediates,
+        });
+        address += 1 + parameters as usize;
+    }
+    instructions
+}
+
+impl ScoreHash {
+    pub fn index(&self, layout: &Layout, point: Point) -> usize {
+        let cell = match self.order {
+            CellOrder::RowMajor => point.y * layout.width + point.x,
+            CellOrder::ColumnMajor => point.x * layout.height + point.y,
+        };
+        (self.multiplier * cell + self.offset) % layout.cells()
+    }
+
+    /// Finds the hash in the code before the tile grid. The arcade numbers the cell by
+    /// multiplying a coordinate by the width or the height, multiplies and adds the two constants,
+    /// then takes the modulo with the number of cells, so these four instructions are looked for
+    /// in this order and close to each other.
+    pub fn from_code(code: &[i64], layout: &Layout) -> Result<ScoreHash, PatchError> {
+        let instructions = decode(&code[..layout.grid.min(code.len())]);
+        let cells = layout.cells() as i64;
+        for (i, instruction) in instructions.iter().enumerate() {
+            let order = match instruction.opcode {
+                MUL if instruction.uses(layout.width as i64) => CellOrder::RowMajor,
+                MUL if instruction.uses(layout.height as i64) => CellOrder::ColumnMajor,
+                _ => continue,
+            };
+            let window = &instructions[i + 1..(i + 1 + HASH_WINDOW).min(instructions.len())];
+            let find = |from: usize, opcode: i64| {
+                window[from..]
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, ins)| ins.opcode == opcode)
+                    .find_map(|(j, ins)| ins.immediate().map(|value| (from + j, value)))
+            };
+            let (m, multiplier) = match find(0, MUL) {
+                Some((m, multiplier)) if multiplier > 0 => (m, multiplier),
+                _ => continue,
+            };
+            let (o, offset) = match find(m + 1, ADD) {
+                Some(found) => found,
+                None => continue,
+            };
+            if window[o + 1..]
+                .iter()
+                .any(|ins| ins.uses(cells) || ins.uses(-cells))
+            {
+                return Ok(ScoreHash {
+                    order,
+                    multiplier: multiplier as usize,
+                    offset: offset.rem_euclid(cells) as usize,
+                });
+            }
+        }
+        Err(PatchError::NoMatchingHash)
+    }
+}
+
+struct Neutral;
+
+impl Controller for Neutral {
+    fn joystick(&mut self, _: &Game) -> i64 {
+        0
+    }
+}
+
+fn insert_coin(code: &[i64]) -> IntcodeComputer {
+    let mut computer = IntcodeComputer::new(Vec::from(code));
+    computer.arbitrary_set(0, 2);
+    computer
+}
+
+fn expected_grid(game: &Game, width: usize, height: usize) -> Vec<i64> {
+    let mut grid = vec![Tile::Empty.to_int(); width * height];
+    for (p, t) in game.screen() {
+        grid[p.y * width + p.x] = t.to_int();
+    }
+    grid[game.paddle.y * width + game.paddle.x] = Tile::Paddle.to_int();
+    grid[game.ball.y * width + game.ball.x] = Tile::Ball.to_int();
+    grid
+}
+
+/// Draws the first screen and looks for a copy of it in memory
+pub fn locate(code: &[i64]) -> Result<Layout, PatchError> {
+    let mut game = Game::new(insert_coin(code));
+    if !game.advance(&mut Neutral)? {
+        return Err(PatchError::EndedEarly);
+    }
+    let (width, height) = game
+        .screen()
+        .keys()
+        .fold((0, 0), |(w, h), p| (w.max(p.x + 1), h.max(p.y + 1)));
+    let inside = |p: Point| p.x < width && p.y < height;
+    if width < 3 || !inside(game.paddle) || !inside(game.ball) {
+        return Err(PatchError::UnexpectedScreen { width, height });
+    }
+    let expected = expected_grid(&game, width, height);
+    let memory = game.computer.memory();
+    let grid = memory
+        .windows(expected.len())
+        .position(|window| window == &expected[..])
+        .ok_or(PatchError::GridNotFound)?;
+    let layout = Layout {
+        grid,
+        width,
+        height,
+        paddle_row: game.paddle.y,
+        score_table: grid + expected.len(),
+    };
+    let needed = layout.score_table + layout.cells();
+    if memory.len() < needed {
+        return Err(PatchError::MemoryTooShort {
+            needed,
+            available: memory.len(),
+        });
+    }
+    Ok(layout)
+}
+
+/// Sums the score table entries of every block initially on screen, without playing
+pub fn analytical_score(code: &[i64], layout: &Layout) -> Result<usize, PatchError> {
+    let hash = ScoreHash::from_code(code, layout)?;
+    let computer = insert_coin(code);
+    let table = layout.score_table(&computer);
+    let mut score = 0;
+    for y in 0..layout.height {
+        for x in 0..layout.width {
+            let point = Point { x, y };
+            if layout.tile(&computer, point)? == Tile::Block {
+                score += table[hash.index(layout, point)] as usize;
+            }
+        }
+    }
+    Ok(score)
+}
+
+#[aoc(day13, part2, patched)]
+pub fn play_the_game_patched(code: &[i64]) -> Result<usize, PatchError> {
+    let layout = locate(code)?;
+    analytical_score(code, &layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(grid: usize) -> Layout {
+        Layout {
+            grid,
+            width: 6,
+            height: 4,
+            paddle_row: 3,
+            score_table: grid + 24,
+        }
+    }
+
+    // [103] = ([100] * coordinate_size + [101]) * 7 + 30 then compared with the cell count
+    fn hash_code(coordinate_size: i64) -> Vec<i64> {
+        let instructions = [
+            [1002, 100, coordinate_size, 103], // mul
+            [1, 103, 101, 103],                // add
+            [1002, 103, 7, 103],               // mul
+            [1001, 103, 30, 103],              // add
+            [1007, 103, 24, 102],              // lt
+        ];
+        let mut code = instructions.concat();
+        code.push(99);
+        code
+    }
+
+    #[test]
+    fn hash_is_read_from_the_code() {
+        let code = hash_code(6);
+        let hash = ScoreHash::from_code(&code, &layout(code.len())).unwrap();
+        assert_eq!(
+            hash,
+            ScoreHash {
+                order: CellOrder::RowMajor,
+                multiplier: 7,
+                offset: 6,
+            }
+        );
+        assert_eq!(
+            hash.index(&layout(0), Point { x: 1, y: 2 }),
+            (7 * 13 + 30) % 24
+        );
+
+        let code = hash_code(4);
+        let hash = ScoreHash::from_code(&code, &layout(code.len())).unwrap();
+        assert_eq!(hash.order, CellOrder::ColumnMajor);
+        assert_eq!(
+            hash.index(&layout(0), Point { x: 1, y: 2 }),
+            (7 * 6 + 30) % 24
+        );
+    }
+
+    #[test]
+    fn hash_needs_the_modulo() {
+        let mut code = hash_code(6);
+        code[18] = 25;
+        let result = ScoreHash::from_code(&code, &layout(code.len()));
+        assert_eq!(result, Err(PatchError::NoMatchingHash));
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::{self, Write};
+
+/// Black and white picture, `true` being a lit pixel
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<bool>,
+}
+
+impl Bitmap {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+    /// Smallest bitmap containing all the lit points, rows going down as `y` increases
+    pub fn from_points<I: IntoIterator<Item = (isize, isize)>>(points: I) -> Self {
+        let points: Vec<_> = points.into_iter().collect();
+        if points.is_empty() {
+            return Self::new(0, 0);
+        }
+        let min_x = points.iter().map(|p| p.0).min().unwrap();
+        let min_y = points.iter().map(|p| p.1).min().unwrap();
+        let max_x = points.iter().map(|p| p.0).max().unwrap();
+        let max_y = points.iter().map(|p| p.1).max().unwrap();
+
+        let mut bitmap = Self::new((max_x - min_x + 1) as usize, (max_y - min_y + 1) as usize);
+        for (x, y) in points {
+            bitmap.set((x - min_x) as usize, (y - min_y) as usize, true);
+        }
+        bitmap
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        self.pixels[y * self.width + x] = lit;
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    /// One line per row, `#` for lit pixels and `.` for the others
+    pub fn to_text(&self) -> String {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Plain (ASCII) PBM, where 1 is black, so lit pixels are written as 0
+    pub fn write_pbm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "P1\n{} {}", self.width, self.height)?;
+        for row in self.rows() {
+            let line: Vec<_> = row.iter().map(|&lit| if lit { "0" } else { "1" }).collect();
+            writeln!(out, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+
+    /// Grayscale PNG, each pixel being a `scale`x`scale` square
+    pub fn write_png<W: Write>(&self, out: W, scale: usize) -> io::Result<()> {
+        let gray: Vec<u8> = self
+            .pixels
+            .iter()
+            .map(|&lit| if lit { 255 } else { 0 })
+            .collect();
+        let data = upscale(gray.chunks(self.width.max(1)), scale);
+        let (width, height) = (self.width * scale, self.height * scale);
+        write_png(out, width, height, png::ColorType::Grayscale, &data)
+    }
+}
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const DARK_GRAY: Rgb = [90, 90, 90];
+pub const GRAY: Rgb = [128, 128, 128];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const BLUE: Rgb = [60, 110, 230];
+pub const ORANGE: Rgb = [220, 120, 40];
+pub const YELLOW: Rgb = [250, 200, 40];
+
+/// Pixels of `rows`, each one repeated as a `scale`x`scale` square
+pub fn upscale<'a, P: Copy + 'a, I: IntoIterator<Item = &'a [P]>>(rows: I, scale: usize) -> Vec<P> {
+    let mut scaled = Vec::new();
+    for row in rows {
+        let start = scaled.len();
+        for &pixel in row {
+            scaled.extend(std::iter::repeat_n(pixel, scale));
+        }
+        let end = scaled.len();
+        for _ in 1..scale {
+            scaled.extend_from_within(start..end);
+        }
+    }
+    scaled
+}
+
+fn write_png<W: Write>(
+    out: W,
+    width: usize,
+    height: usize,
+    color: png::ColorType,
+    data: &[u8],
+) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(data)?;
+    Ok(())
+}
+
+/// Colour picture, rows going down as `y` increases
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Raster {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Raster {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    fn rgb(&self, scale: usize) -> Vec<u8> {
+        upscale(self.rows(), scale).concat()
+    }
+    /// Binary PPM, each pixel being a `scale`x`scale` square
+    pub fn write_ppm<W: Write>(&self, out: &mut W, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.width * scale, self.height * scale);
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+        out.write_all(&self.rgb(scale))
+    }
+    pub fn write_png<W: Write>(&self, out: W, scale: usize) -> io::Result<()> {
+        let (width, height) = (self.width * scale, self.height * scale);
+        write_png(out, width, height, png::ColorType::RGB, &self.rgb(scale))
+    }
+}
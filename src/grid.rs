@@ -0,0 +1,410 @@
+//! Positions and directions on a 2D grid.
+//!
+//! `x` grows to the right and `y` grows downwards, like the rows of a puzzle input or of the
+//! terminal: `Up` decreases `y` and rows are rendered from the smallest `y` to the largest.
+//! Turning right is therefore clockwise as seen on screen.
+
+use num::{Num, Signed, ToPrimitive};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+fn abs_diff<T: Num + PartialOrd + Copy>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Num + PartialOrd + Copy> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+    pub fn origin() -> Self {
+        Point::new(T::zero(), T::zero())
+    }
+    pub fn manhattan(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+    pub fn distance_squared(&self, other: &Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        dx * dx + dy * dy
+    }
+}
+
+impl<T: Num + PartialOrd + Copy + ToPrimitive> Point<T> {
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        self.distance_squared(other)
+            .to_f64()
+            .expect("distance does not fit in a f64")
+            .sqrt()
+    }
+}
+
+impl<T: Signed + PartialOrd + Copy> Point<T> {
+    pub fn step(self, direction: Direction) -> Self {
+        self.step_by(direction, T::one())
+    }
+    pub fn step_by(self, direction: Direction, length: T) -> Self {
+        let (dx, dy) = direction.offset::<T>();
+        Point {
+            x: self.x + dx * length,
+            y: self.y + dy * length,
+        }
+    }
+    /// The four orthogonal neighbours, in the order of `Direction::ALL`
+    pub fn neighbours(self) -> [Self; 4] {
+        [
+            self.step(Direction::Up),
+            self.step(Direction::Right),
+            self.step(Direction::Down),
+            self.step(Direction::Left),
+        ]
+    }
+    /// Direction leading to `other` if it is a neighbour
+    pub fn direction_to(&self, other: &Self) -> Option<Direction> {
+        Direction::ALL
+            .iter()
+            .copied()
+            .find(|&d| self.step(d) == *other)
+    }
+}
+
+impl Point<isize> {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// In clockwise order, starting from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn offset<T: Signed>(self) -> (T, T) {
+        match self {
+            Direction::Up => (T::zero(), -T::one()),
+            Direction::Right => (T::one(), T::zero()),
+            Direction::Down => (T::zero(), T::one()),
+            Direction::Left => (-T::one(), T::zero()),
+        }
+    }
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+/// Smallest rectangle containing a set of points, both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox<T = isize> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Num + PartialOrd + Copy> BoundingBox<T> {
+    pub fn from_points<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Point<T>>,
+        T: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+        Some(points.fold(
+            BoundingBox {
+                min: first,
+                max: first,
+            },
+            |bounds, p| bounds.including(*p),
+        ))
+    }
+    pub fn including(self, p: Point<T>) -> Self {
+        let min = |a, b| if a < b { a } else { b };
+        let max = |a, b| if a > b { a } else { b };
+        BoundingBox {
+            min: Point::new(min(self.min.x, p.x), min(self.min.y, p.y)),
+            max: Point::new(max(self.max.x, p.x), max(self.max.y, p.y)),
+        }
+    }
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x + T::one()
+    }
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y + T::one()
+    }
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+}
+
+impl BoundingBox<isize> {
+    pub fn grow(self, margin: isize) -> Self {
+        BoundingBox {
+            min: Point::new(self.min.x - margin, self.min.y - margin),
+            max: Point::new(self.max.x + margin, self.max.y + margin),
+        }
+    }
+    /// Every point inside the box, row by row
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point { x, y }))
+    }
+}
+
+/// Rectangular grid storing a value for each cell, starting at (0, 0)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Fails if the rows are not all the same length
+    pub fn from_rows<I, R>(rows: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for row in rows {
+            let before = cells.len();
+            cells.extend(row);
+            let row_width = cells.len() - before;
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(format!(
+                        "Row {} has {} cells instead of {}",
+                        height, row_width, w
+                    ))
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    fn index(&self, p: Point<usize>) -> Option<usize> {
+        if p.x < self.width && p.y < self.height {
+            Some(p.y * self.width + p.x)
+        } else {
+            None
+        }
+    }
+    pub fn get(&self, p: Point<usize>) -> Option<&T> {
+        self.index(p).map(|i| &self.cells[i])
+    }
+    pub fn get_mut(&mut self, p: Point<usize>) -> Option<&mut T> {
+        self.index(p).map(move |i| &mut self.cells[i])
+    }
+    /// Panics if `p` is outside the grid
+    pub fn set(&mut self, p: Point<usize>, value: T) {
+        let i = self.index(p).expect("point outside of the grid");
+        self.cells[i] = value;
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, v)| (Point::new(i % width, i / width), v))
+    }
+}
+
+/// Grid only storing the cells that were set, which can be anywhere
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get(&self, p: &Point) -> Option<&T> {
+        self.cells.get(p)
+    }
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+    pub fn remove(&mut self, p: &Point) -> Option<T> {
+        self.cells.remove(p)
+    }
+    pub fn contains(&self, p: &Point) -> bool {
+        self.cells.contains_key(p)
+    }
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells.iter()
+    }
+    pub fn points(&self) -> impl Iterator<Item = &Point> {
+        self.cells.keys()
+    }
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(self.cells.keys())
+    }
+}
+
+impl<T> std::iter::FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> impl Iterator<Item = Point> {
+        (-3..=3).flat_map(|y| (-3..=3).map(move |x| Point::new(x, y)))
+    }
+
+    #[test]
+    fn turns_are_inverse() {
+        for &d in &Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right().turn_right().turn_right(), d);
+            assert_eq!(d.turn_left().turn_left().turn_left().turn_left(), d);
+        }
+    }
+
+    #[test]
+    fn turning_right_is_clockwise_on_screen() {
+        for (i, &d) in Direction::ALL.iter().enumerate() {
+            assert_eq!(d.turn_right(), Direction::ALL[(i + 1) % 4]);
+        }
+        assert_eq!(Point::ORIGIN.step(Direction::Up), Point::new(0, -1));
+        assert_eq!(
+            Point::ORIGIN.step(Direction::Up.turn_right()),
+            Point::new(1, 0)
+        );
+    }
+
+    #[test]
+    fn reverse_is_two_turns() {
+        for &d in &Direction::ALL {
+            assert_eq!(d.reverse(), d.turn_right().turn_right());
+            assert_eq!(d.reverse(), d.turn_left().turn_left());
+            assert_ne!(d.reverse(), d);
+        }
+    }
+
+    #[test]
+    fn stepping_back_returns() {
+        for p in points() {
+            for &d in &Direction::ALL {
+                assert_eq!(p.step(d).step(d.reverse()), p);
+                assert_eq!(p.step_by(d, 3), p.step(d).step(d).step(d));
+            }
+        }
+    }
+
+    #[test]
+    fn direction_to_neighbour() {
+        for p in points() {
+            for &d in &Direction::ALL {
+                assert_eq!(p.direction_to(&p.step(d)), Some(d));
+            }
+            assert_eq!(p.direction_to(&p), None);
+            assert_eq!(p.direction_to(&Point::new(p.x + 1, p.y + 1)), None);
+        }
+    }
+
+    #[test]
+    fn neighbours_follow_all_directions() {
+        for p in points() {
+            let expected: Vec<_> = Direction::ALL.iter().map(|&d| p.step(d)).collect();
+            assert_eq!(p.neighbours().to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn bounding_box_contains_points() {
+        let all: Vec<_> = points().collect();
+        for n in 1..all.len() {
+            // a scattered subset of the points
+            let subset: Vec<_> = all.iter().copied().step_by(n).collect();
+            let bounds = BoundingBox::from_points(&subset).unwrap();
+            assert!(subset.iter().all(|p| bounds.contains(p)));
+            assert!(subset.iter().any(|p| p.x == bounds.min.x));
+            assert!(subset.iter().any(|p| p.y == bounds.max.y));
+        }
+        assert_eq!(BoundingBox::<isize>::from_points(&[]), None);
+    }
+}
@@ -1,4 +1,6 @@
 type Layer = Vec<Vec<u8>>;
+use crate::bitmap::Bitmap;
+use crate::ocr;
 use itertools::Itertools;
 
 #[aoc_generator(day8)]
@@ -70,21 +72,17 @@ fn create_image(encoded: &Vec<Layer>) -> Layer {
     }
     current_image
 }
+fn to_bitmap(image: &Layer) -> Bitmap {
+    let mut bitmap = Bitmap::new(image[0].len(), image.len());
+    for (y, line) in image.iter().enumerate() {
+        for (x, d) in line.iter().enumerate() {
+            bitmap.set(x, y, *d == 1);
+        }
+    }
+    bitmap
+}
 #[aoc(day8, part2)]
-pub fn find_password_in_image(input: &Vec<Layer>) -> String {
+pub fn find_password_in_image(input: &Vec<Layer>) -> Result<String, String> {
     let image = create_image(input);
-    format!(
-        "\n{}",
-        image
-            .iter()
-            .map(|line| line
-                .iter()
-                .map(|d| match d {
-                    0 => yansi::Paint::default(" "),
-                    1 => yansi::Paint::new(" ").bg(yansi::Color::White),
-                    _ => unreachable!(),
-                })
-                .format(""))
-            .format("\n")
-    )
+    ocr::read_letters(&to_bitmap(&image))
 }
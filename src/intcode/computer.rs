@@ -203,6 +203,9 @@ impl IntcodeComputer {
     pub fn arbitrary_set(&mut self, index: usize, value: i64) {
         self.memory[index] = value;
     }
+    pub fn memory(&self) -> &[i64] {
+        &self.memory
+    }
     #[inline]
     fn resolve_relative(&self, offset: i64) -> usize {
         (self.relative_base + offset) as usize
@@ -1,10 +1,5 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
+use crate::grid::{Direction, Point};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Movement {
     direction: Direction,
@@ -35,65 +30,20 @@ pub fn get_paths(input: &str) -> (Path, Path) {
     });
     (paths.next().unwrap(), paths.next().unwrap())
 }
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
 use std::collections::HashMap;
 
-fn go_to(start: Point, movement: Movement) -> (Point, Box<dyn Iterator<Item = Point>>) {
-    let mut end_point = start;
-    match movement.direction {
-        Direction::Up => {
-            end_point.y += movement.length;
-            (
-                end_point,
-                Box::new((0..=movement.length).map(move |i| Point {
-                    x: start.x,
-                    y: start.y + i,
-                })),
-            )
-        }
-        Direction::Down => {
-            end_point.y -= movement.length;
-            (
-                end_point,
-                Box::new((0..=movement.length).map(move |i| Point {
-                    x: start.x,
-                    y: start.y - i,
-                })),
-            )
-        }
-        Direction::Left => {
-            end_point.x -= movement.length;
-            (
-                end_point,
-                Box::new((0..=movement.length).map(move |i| Point {
-                    x: start.x - i,
-                    y: start.y,
-                })),
-            )
-        }
-        Direction::Right => {
-            end_point.x += movement.length;
-            (
-                end_point,
-                Box::new((0..=movement.length).map(move |i| Point {
-                    x: start.x + i,
-                    y: start.y,
-                })),
-            )
-        }
-    }
+fn go_to(start: Point<i64>, movement: Movement) -> (Point<i64>, impl Iterator<Item = Point<i64>>) {
+    (
+        start.step_by(movement.direction, movement.length),
+        (0..=movement.length).map(move |i| start.step_by(movement.direction, i)),
+    )
 }
 
 use std::collections::HashSet;
-fn run_path(path: &Path) -> (HashSet<Point>, HashMap<Point, i64>) {
+fn run_path(path: &Path) -> (HashSet<Point<i64>>, HashMap<Point<i64>, i64>) {
     let mut points = HashSet::new();
     let mut steps = HashMap::new();
-    let mut current_point = Point { x: 0, y: 0 };
+    let mut current_point = Point::origin();
     steps.insert(current_point, 0);
     for movement in path {
         let (new_point, passed_points) = go_to(current_point, *movement);
@@ -113,11 +63,11 @@ fn run_path(path: &Path) -> (HashSet<Point>, HashMap<Point, i64>) {
 #[aoc(day3, part1)]
 pub fn find_nearest_intersection((path1, path2): &(Path, Path)) -> i64 {
     let (mut passed1, _) = run_path(path1);
-    passed1.remove(&Point { x: 0, y: 0 });
+    passed1.remove(&Point::origin());
     let (passed2, _) = run_path(path2);
     passed1
         .intersection(&passed2)
-        .map(|Point { x, y }| (x + y).abs())
+        .map(|p| p.manhattan(&Point::origin()))
         .min()
         .unwrap()
 }
@@ -125,7 +75,7 @@ pub fn find_nearest_intersection((path1, path2): &(Path, Path)) -> i64 {
 #[aoc(day3, part2)]
 pub fn find_intersection_with_least_steps((path1, path2): &(Path, Path)) -> i64 {
     let (mut passed1, steps1) = run_path(path1);
-    passed1.remove(&Point { x: 0, y: 0 });
+    passed1.remove(&Point::origin());
     let (passed2, steps2) = run_path(path2);
     passed1
         .intersection(&passed2)
@@ -133,3 +83,25 @@ pub fn find_intersection_with_least_steps((path1, path2): &(Path, Path)) -> i64
         .min()
         .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_intersection_examples() {
+        let paths = get_paths("R8,U5,L5,D3\nU7,R6,D4,L4");
+        assert_eq!(find_nearest_intersection(&paths), 6);
+        let paths = get_paths(
+            "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+        );
+        assert_eq!(find_nearest_intersection(&paths), 159);
+    }
+
+    #[test]
+    fn nearest_intersection_in_mixed_quadrants() {
+        // crossing 3 right and 3 down, where the sum of the coordinates cancels out
+        let paths = get_paths("R3,D5\nD3,R5");
+        assert_eq!(find_nearest_intersection(&paths), 6);
+    }
+}
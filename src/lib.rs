@@ -2,12 +2,16 @@
 extern crate aoc_runner_derive;
 
 pub mod asteroid;
+pub mod bitmap;
+pub mod grid;
 pub mod image;
 pub mod intcode;
 pub mod intersectin_wires;
+pub mod ocr;
 pub mod orbits;
 pub mod painting_robot;
 pub mod password;
+pub mod render;
 pub mod simulate_planets;
 pub mod ball_breaker;
 pub mod fuel_creation;
@@ -0,0 +1,451 @@
+use crate::bitmap::Bitmap;
+
+struct Font {
+    height: usize,
+    // distance between the start of two consecutive letters
+    stride: usize,
+    // rows of each letter, most are as wide as the font but some also fill the gap to the next one
+    glyphs: &'static [(char, &'static str)],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    stride: 5,
+    glyphs: &[
+        ('A', concat!(
+            ".##.",
+            "#..#",
+            "#..#",
+            "####",
+            "#..#",
+            "#..#",
+        )),
+        ('B', concat!(
+            "###.",
+            "#..#",
+            "###.",
+            "#..#",
+            "#..#",
+            "###.",
+        )),
+        ('C', concat!(
+            ".##.",
+            "#..#",
+            "#...",
+            "#...",
+            "#..#",
+            ".##.",
+        )),
+        ('E', concat!(
+            "####",
+            "#...",
+            "###.",
+            "#...",
+            "#...",
+            "####",
+        )),
+        ('F', concat!(
+            "####",
+            "#...",
+            "###.",
+            "#...",
+            "#...",
+            "#...",
+        )),
+        ('G', concat!(
+            ".##.",
+            "#..#",
+            "#...",
+            "#.##",
+            "#..#",
+            ".###",
+        )),
+        ('H', concat!(
+            "#..#",
+            "#..#",
+            "####",
+            "#..#",
+            "#..#",
+            "#..#",
+        )),
+        ('I', concat!(
+            ".###",
+            "..#.",
+            "..#.",
+            "..#.",
+            "..#.",
+            ".###",
+        )),
+        ('J', concat!(
+            "..##",
+            "...#",
+            "...#",
+            "...#",
+            "#..#",
+            ".##.",
+        )),
+        ('K', concat!(
+            "#..#",
+            "#.#.",
+            "##..",
+            "#.#.",
+            "#.#.",
+            "#..#",
+        )),
+        ('L', concat!(
+            "#...",
+            "#...",
+            "#...",
+            "#...",
+            "#...",
+            "####",
+        )),
+        ('O', concat!(
+            ".##.",
+            "#..#",
+            "#..#",
+            "#..#",
+            "#..#",
+            ".##.",
+        )),
+        ('P', concat!(
+            "###.",
+            "#..#",
+            "#..#",
+            "###.",
+            "#...",
+            "#...",
+        )),
+        ('R', concat!(
+            "###.",
+            "#..#",
+            "#..#",
+            "###.",
+            "#.#.",
+            "#..#",
+        )),
+        ('S', concat!(
+            ".###",
+            "#...",
+            "#...",
+            ".##.",
+            "...#",
+            "###.",
+        )),
+        ('U', concat!(
+            "#..#",
+            "#..#",
+            "#..#",
+            "#..#",
+            "#..#",
+            ".##.",
+        )),
+        ('Y', concat!(
+            "#...#",
+            "#...#",
+            ".#.#.",
+            "..#..",
+            "..#..",
+            "..#..",
+        )),
+        ('Z', concat!(
+            "####",
+            "...#",
+            "..#.",
+            ".#..",
+            "#...",
+            "####",
+        )),
+    ],
+};
+
+const LARGE: Font = Font {
+    height: 10,
+    stride: 8,
+    glyphs: &[
+        ('A', concat!(
+            "..##..",
+            ".#..#.",
+            "#....#",
+            "#....#",
+            "#....#",
+            "######",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#....#",
+        )),
+        ('B', concat!(
+            "#####.",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#####.",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#####.",
+        )),
+        ('C', concat!(
+            ".####.",
+            "#....#",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#....#",
+            ".####.",
+        )),
+        ('E', concat!(
+            "######",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#####.",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "######",
+        )),
+        ('F', concat!(
+            "######",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#####.",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+        )),
+        ('G', concat!(
+            ".####.",
+            "#....#",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#..###",
+            "#....#",
+            "#....#",
+            "#...##",
+            ".###.#",
+        )),
+        ('H', concat!(
+            "#....#",
+            "#....#",
+            "#....#",
+            "#....#",
+            "######",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#....#",
+        )),
+        ('J', concat!(
+            "...###",
+            "....#.",
+            "....#.",
+            "....#.",
+            "....#.",
+            "....#.",
+            "....#.",
+            "#...#.",
+            "#...#.",
+            ".###..",
+        )),
+        ('K', concat!(
+            "#....#",
+            "#...#.",
+            "#..#..",
+            "#.#...",
+            "##....",
+            "##....",
+            "#.#...",
+            "#..#..",
+            "#...#.",
+            "#....#",
+        )),
+        ('L', concat!(
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "######",
+        )),
+        ('N', concat!(
+            "#....#",
+            "##...#",
+            "##...#",
+            "#.#..#",
+            "#.#..#",
+            "#..#.#",
+            "#..#.#",
+            "#...##",
+            "#...##",
+            "#....#",
+        )),
+        ('P', concat!(
+            "#####.",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#####.",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+            "#.....",
+        )),
+        ('R', concat!(
+            "#####.",
+            "#....#",
+            "#....#",
+            "#....#",
+            "#####.",
+            "#..#..",
+            "#...#.",
+            "#...#.",
+            "#....#",
+            "#....#",
+        )),
+        ('X', concat!(
+            "#....#",
+            "#....#",
+            ".#..#.",
+            ".#..#.",
+            "..##..",
+            "..##..",
+            ".#..#.",
+            ".#..#.",
+            "#....#",
+            "#....#",
+        )),
+        ('Z', concat!(
+            "######",
+            ".....#",
+            ".....#",
+            "....#.",
+            "...#..",
+            "..#...",
+            ".#....",
+            "#.....",
+            "#.....",
+            "######",
+        )),
+    ],
+};
+
+impl Font {
+    fn matches(&self, glyph: &str, bitmap: &Bitmap, left: isize) -> bool {
+        let width = glyph.len() / self.height;
+        glyph.chars().enumerate().all(|(i, c)| {
+            let x = left + (i % width) as isize;
+            let y = i / width;
+            let lit = x >= 0 && bitmap.get(x as usize, y);
+            lit == (c == '#')
+        })
+    }
+    fn read_letter(&self, bitmap: &Bitmap, left: isize) -> Option<char> {
+        self.glyphs
+            .iter()
+            .find(|(_, glyph)| self.matches(glyph, bitmap, left))
+            .map(|(letter, _)| *letter)
+    }
+    fn read(&self, bitmap: &Bitmap) -> Option<String> {
+        // The bitmap may have been cropped to its lit pixels, hiding the start of the first letter
+        (0..self.stride).find_map(|shift| {
+            let letters = (bitmap.width() + shift).div_ceil(self.stride);
+            (0..letters)
+                .map(|i| self.read_letter(bitmap, (i * self.stride) as isize - shift as isize))
+                .collect()
+        })
+    }
+}
+
+/// Reads the capital letters drawn in the 4x6 or 6x10 block font
+pub fn read_letters(bitmap: &Bitmap) -> Result<String, String> {
+    let font = match bitmap.height() {
+        h if h == SMALL.height => SMALL,
+        h if h == LARGE.height => LARGE,
+        h => return Err(format!("No font is {} pixels high", h)),
+    };
+    font.read(bitmap)
+        .ok_or_else(|| format!("Unknown letters in:\n{}", bitmap.to_text()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(rows: &[&str]) -> Bitmap {
+        let mut bitmap = Bitmap::new(rows[0].len(), rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                bitmap.set(x, y, c == '#');
+            }
+        }
+        bitmap
+    }
+
+    // Same letters with the blank columns around them removed
+    fn cropped(bitmap: &Bitmap) -> Bitmap {
+        let lit = (0..bitmap.height()).flat_map(|y| {
+            (0..bitmap.width())
+                .filter(move |&x| bitmap.get(x, y))
+                .map(move |x| (x as isize, y as isize))
+        });
+        Bitmap::from_points(lit.collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let letters = bitmap(&[
+            ".###.#...#.##..####.",
+            "..#..#...##..#....#.",
+            "..#...#.#.#..#...#..",
+            "..#....#..####..#...",
+            "..#....#..#..#.#....",
+            ".###...#..#..#.####.",
+        ]);
+        assert_eq!(read_letters(&letters), Ok("IYAZ".to_owned()));
+        let letters = cropped(&letters);
+        assert_eq!(letters.width(), 18);
+        assert_eq!(read_letters(&letters), Ok("IYAZ".to_owned()));
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let letters = bitmap(&[
+            "...###....##....######..######..",
+            "....#....#..#........#.......#..",
+            "....#...#....#.......#.......#..",
+            "....#...#....#......#.......#...",
+            "....#...#....#.....#.......#....",
+            "....#...######....#.......#.....",
+            "....#...#....#...#.......#......",
+            "#...#...#....#..#.......#.......",
+            "#...#...#....#..#.......#.......",
+            ".###....#....#..######..######..",
+        ]);
+        assert_eq!(read_letters(&letters), Ok("JAZZ".to_owned()));
+        let letters = cropped(&letters);
+        assert_eq!(letters.width(), 30);
+        assert_eq!(read_letters(&letters), Ok("JAZZ".to_owned()));
+    }
+
+    #[test]
+    fn rejects_unknown_height() {
+        assert!(read_letters(&Bitmap::new(5, 7)).is_err());
+    }
+}
@@ -1,75 +1,57 @@
+use crate::bitmap::Bitmap;
+use crate::grid::{BoundingBox, Direction, Point};
+use crate::ocr;
+use crate::render::{Cell, Renderer};
 use crate::intcode::{IntcodeState, IntcodeComputer, parse_intcode};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-impl Point {
-    fn advance(&self, direction: Direction) -> Self {
-        let (dx, dy) = match direction {
-            Direction::Up => (0, 1),
-            Direction::Left => (1, 0),
-            Direction::Down => (0, -1),
-            Direction::Right => (-1, 0),
-        };
-        Point {
-            x: self.x + dx,
-            y: self.y + dy,
-        }
-    }
+pub enum Color {
+    White,
+    Black,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum Direction {
-    Up,
+pub enum Turn {
     Left,
     Right,
-    Down,
 }
+
+/// Everything that happened while the robot was on one panel
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum Color {
-    White,
-    Black,
+pub struct Step {
+    pub position: Point,
+    pub direction: Direction,
+    pub read: Color,
+    pub painted: Color,
+    pub turn: Turn,
 }
 
-impl Direction {
-    fn clockwise(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
-    }
-    fn counter_clockwise(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Left,
-            Direction::Left => Direction::Down,
-            Direction::Down => Direction::Right,
-            Direction::Right => Direction::Up,
-        }
-    }
-}
 pub struct Robot {
     computer: IntcodeComputer,
     position: Point,
     direction: Direction,
 
     white: HashSet<Point>,
+    history: Option<Vec<Step>>,
 }
 
 impl Robot {
     fn new(computer: IntcodeComputer) -> Self {
         Self {
             computer,
-            position: Point{x: 0, y: 0},
+            position: Point::ORIGIN,
             direction: Direction::Up,
 
             white: HashSet::new(),
+            history: None,
         }
     }
+    fn with_history(computer: IntcodeComputer) -> Self {
+        let mut robot = Self::new(computer);
+        robot.history = Some(Vec::new());
+        robot
+    }
     fn next_output(&mut self) -> Option<i64> {
         loop {
             match self.computer.step() {
@@ -81,8 +63,12 @@ impl Robot {
         }
     }
     fn step(&mut self) -> bool {
-        let input = self.white.contains(&self.position) as i64;
-        self.computer.add_input(input);
+        let read = if self.white.contains(&self.position) {
+            Color::White
+        } else {
+            Color::Black
+        };
+        self.computer.add_input((read == Color::White) as i64);
         let color = match self.next_output() {
             Some(0) => Color::Black,
             Some(1) => Color::White,
@@ -93,13 +79,26 @@ impl Robot {
             Color::White => self.white.insert(self.position),
             Color::Black => self.white.remove(&self.position),
         };
-        self.direction = match self.next_output() {
-            Some(0) => self.direction.clockwise(),
-            Some(1) => self.direction.counter_clockwise(),
+        let turn = match self.next_output() {
+            Some(0) => Turn::Left,
+            Some(1) => Turn::Right,
             Some(i) => panic!("Invalid turning point: {}", i),
             None => return false,
         };
-        self.position = self.position.advance(self.direction);
+        if let Some(history) = &mut self.history {
+            history.push(Step {
+                position: self.position,
+                direction: self.direction,
+                read,
+                painted: color,
+                turn,
+            });
+        }
+        self.direction = match turn {
+            Turn::Left => self.direction.turn_left(),
+            Turn::Right => self.direction.turn_right(),
+        };
+        self.position = self.position.step(self.direction);
         true
     }
     fn run(&mut self) {
@@ -119,31 +118,140 @@ pub fn count_paint(input: &[i64]) -> usize {
     been_at.len()
 }
 
-fn draw_image(white: &HashSet<Point>) {
-    let min_y = white.iter().map(|p| p.y).min().unwrap();
-    let min_x = white.iter().map(|p| p.x).min().unwrap();
-
-    let max_y = white.iter().map(|p| p.y).max().unwrap();
-    let max_x = white.iter().map(|p| p.x).max().unwrap();
-    for y in (min_y..=max_y).rev() {
-        for x in min_x..=max_x {
-            let pixel = if white.contains(&Point{x, y}) {
-                yansi::Paint::new(" ").bg(yansi::Color::White)
-            } else {
-                yansi::Paint::new(" ")
-            };
-            print!("{}", pixel);
-        }
-        print!("\n");
-    }
+fn hull_bitmap(white: &HashSet<Point>) -> Bitmap {
+    Bitmap::from_points(white.iter().map(|p| (p.x, p.y)))
 }
 
-#[aoc(day11, part2)]
-pub fn show_paint(input: &[i64]) -> &'static str {
+/// Runs the robot starting on a white panel and returns the painted hull
+pub fn paint_hull(input: &[i64]) -> Bitmap {
     let computer = IntcodeComputer::new(Vec::from(input));
     let mut robot = Robot::new(computer);
     robot.white.insert(robot.position);
     robot.run();
-    draw_image(&robot.white);
-    ""
+    hull_bitmap(&robot.white)
+}
+
+#[aoc(day11, part2)]
+pub fn show_paint(input: &[i64]) -> Result<String, String> {
+    ocr::read_letters(&paint_hull(input))
+}
+
+/// Runs the robot while keeping track of every step it takes
+pub fn record_paint(input: &[i64], start_on_white: bool) -> Vec<Step> {
+    let computer = IntcodeComputer::new(Vec::from(input));
+    let mut robot = Robot::with_history(computer);
+    if start_on_white {
+        robot.white.insert(robot.position);
+    }
+    robot.run();
+    robot.history.unwrap_or_default()
+}
+
+/// How many times each panel was painted, whatever the colour
+pub fn paint_counts(history: &[Step]) -> HashMap<Point, usize> {
+    let mut counts = HashMap::new();
+    for step in history {
+        *counts.entry(step.position).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn robot_glyph(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        Direction::Right => '>',
+    }
+}
+
+fn render_frame(white: &HashSet<Point>, robot: &Step, bounds: BoundingBox) -> String {
+    let white_cell = Cell::new(' ').plain('#').bg(yansi::Color::White);
+    let cells = white.iter().map(|p| (*p, white_cell)).collect();
+    let mut robot_cell = Cell::new(robot_glyph(robot.direction));
+    if white.contains(&robot.position) {
+        robot_cell = robot_cell.fg(yansi::Color::Black).bg(yansi::Color::White);
+    }
+    Renderer::new()
+        .bounds(bounds)
+        .highlight(robot.position, robot_cell)
+        .render(&cells)
+}
+
+/// Draws the hull after each step in the terminal, waiting `delay` between frames
+pub fn playback(history: &[Step], delay: std::time::Duration) {
+    if history.is_empty() {
+        return;
+    }
+    let bounds = BoundingBox::from_points(history.iter().map(|s| &s.position)).unwrap();
+
+    // Panels are white before being painted if the robot first saw them white
+    let mut seen = HashSet::new();
+    let mut white: HashSet<Point> = history
+        .iter()
+        .filter(|s| seen.insert(s.position) && s.read == Color::White)
+        .map(|s| s.position)
+        .collect();
+    for (i, step) in history.iter().enumerate() {
+        print!("\x1b[2J\x1b[H{}", render_frame(&white, step, bounds));
+        println!("step {}/{}", i + 1, history.len());
+        std::thread::sleep(delay);
+        match step.painted {
+            Color::White => white.insert(step.position),
+            Color::Black => white.remove(&step.position),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::SparseGrid;
+
+    // Outputs (colour, turn) pairs without reading the camera, painting an L starting from the
+    // origin facing up: paints (0, 0), turns left three times to come back one row lower, then
+    // goes down and right
+    fn l_painter() -> Vec<i64> {
+        let outputs = [1, 0, 0, 0, 0, 0, 1, 1, 1, 0, 1, 0];
+        outputs
+            .iter()
+            .flat_map(|&o| vec![104, o])
+            .chain(std::iter::once(99))
+            .collect()
+    }
+
+    #[test]
+    fn robot_turns_and_moves_on_screen() {
+        let history = record_paint(&l_painter(), true);
+        let path: Vec<_> = history.iter().map(|s| (s.position, s.direction)).collect();
+        assert_eq!(
+            path,
+            vec![
+                (Point::new(0, 0), Direction::Up),
+                (Point::new(-1, 0), Direction::Left),
+                (Point::new(-1, 1), Direction::Down),
+                (Point::new(0, 1), Direction::Right),
+                (Point::new(0, 2), Direction::Down),
+                (Point::new(1, 2), Direction::Right),
+            ]
+        );
+    }
+
+    #[test]
+    fn hull_is_upright() {
+        let hull = paint_hull(&l_painter());
+        assert_eq!(hull.to_text(), "#.\n#.\n##");
+
+        let white: HashSet<_> = [(0, 0), (0, 1), (0, 2), (1, 2)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y))
+            .collect();
+        assert_eq!(hull_bitmap(&white), hull);
+        let cells: SparseGrid<Cell> = white.iter().map(|p| (*p, Cell::new('#'))).collect();
+        let drawn = Renderer::new()
+            .plain()
+            .background(Cell::new('.'))
+            .render(&cells);
+        assert_eq!(drawn, "#.\n#.\n##\n");
+    }
 }
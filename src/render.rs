@@ -0,0 +1,179 @@
+use crate::grid::{BoundingBox, Point, SparseGrid};
+use std::collections::HashMap;
+use yansi::{Color, Paint};
+
+/// What is drawn for a single point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    glyph: char,
+    // used instead of the glyph when colours are not available
+    plain: char,
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    underline: bool,
+}
+
+impl Cell {
+    pub fn new(glyph: char) -> Self {
+        Cell {
+            glyph,
+            plain: glyph,
+            fg: None,
+            bg: None,
+            bold: false,
+            underline: false,
+        }
+    }
+    /// Glyph shown in plain text, useful when the cell is only a background colour
+    pub fn plain(mut self, plain: char) -> Self {
+        self.plain = plain;
+        self
+    }
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    fn write(&self, ansi: bool, out: &mut String) {
+        if !ansi {
+            out.push(self.plain);
+            return;
+        }
+        let mut paint = Paint::new(self.glyph);
+        if let Some(fg) = self.fg {
+            paint = paint.fg(fg);
+        }
+        if let Some(bg) = self.bg {
+            paint = paint.bg(bg);
+        }
+        if self.bold {
+            paint = paint.bold();
+        }
+        if self.underline {
+            paint = paint.underline();
+        }
+        out.push_str(&paint.to_string());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// First row is the smallest `y`, like the grid module and puzzle inputs
+    YDown,
+    /// First row is the largest `y`
+    YUp,
+}
+
+/// Draws a sparse grid of cells, either with ANSI colours or as plain text
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    orientation: Orientation,
+    ansi: bool,
+    margin: isize,
+    bounds: Option<BoundingBox>,
+    background: Cell,
+    highlights: HashMap<Point, Cell>,
+    legend: Vec<(Cell, String)>,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer {
+            orientation: Orientation::YDown,
+            ansi: true,
+            margin: 0,
+            bounds: None,
+            background: Cell::new(' '),
+            highlights: HashMap::new(),
+            legend: Vec::new(),
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    pub fn plain(mut self) -> Self {
+        self.ansi = false;
+        self
+    }
+    /// Extra space drawn around the cells
+    pub fn margin(mut self, margin: isize) -> Self {
+        self.margin = margin;
+        self
+    }
+    /// Draws exactly this area instead of the one computed from the cells
+    pub fn bounds(mut self, bounds: BoundingBox) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+    /// Cell drawn where there is nothing
+    pub fn background(mut self, cell: Cell) -> Self {
+        self.background = cell;
+        self
+    }
+    /// Cell drawn at `point` over whatever is there
+    pub fn highlight(mut self, point: Point, cell: Cell) -> Self {
+        self.highlights.insert(point, cell);
+        self
+    }
+    pub fn legend(mut self, cell: Cell, label: &str) -> Self {
+        self.legend.push((cell, label.to_owned()));
+        self
+    }
+
+    pub fn render(&self, cells: &SparseGrid<Cell>) -> String {
+        let bounds = match self.bounds {
+            Some(bounds) => bounds,
+            None => {
+                let points = cells.points().chain(self.highlights.keys());
+                match BoundingBox::from_points(points) {
+                    Some(bounds) => bounds.grow(self.margin),
+                    None => return String::new(),
+                }
+            }
+        };
+        let rows: Box<dyn Iterator<Item = isize>> = match self.orientation {
+            Orientation::YDown => Box::new(bounds.min.y..=bounds.max.y),
+            Orientation::YUp => Box::new((bounds.min.y..=bounds.max.y).rev()),
+        };
+
+        let mut out = String::new();
+        for y in rows {
+            for x in bounds.min.x..=bounds.max.x {
+                let point = Point { x, y };
+                self.highlights
+                    .get(&point)
+                    .or_else(|| cells.get(&point))
+                    .unwrap_or(&self.background)
+                    .write(self.ansi, &mut out);
+            }
+            out.push('\n');
+        }
+        for (cell, label) in &self.legend {
+            cell.write(self.ansi, &mut out);
+            out.push(' ');
+            out.push_str(label);
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,112 @@
+use super::{oxygen, pathfinding, State};
+use crate::grid::{Point, SparseGrid};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// What the droid learned about the area, the droid starting at the origin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Maze {
+    known: SparseGrid<State>,
+}
+
+impl Maze {
+    pub fn new(known: SparseGrid<State>) -> Self {
+        Maze { known }
+    }
+    pub fn insert(&mut self, point: Point, state: State) {
+        self.known.insert(point, state);
+    }
+    pub fn contains(&self, point: &Point) -> bool {
+        self.known.contains(point)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &State)> {
+        self.known.iter()
+    }
+    pub fn get(&self, point: &Point) -> Option<State> {
+        self.known.get(point).copied()
+    }
+    pub fn is_open(&self, point: &Point) -> bool {
+        match self.get(point) {
+            Some(State::Empty) | Some(State::Tank) => true,
+            Some(State::Wall) | None => false,
+        }
+    }
+    pub fn tank(&self) -> Option<Point> {
+        self.known
+            .iter()
+            .find(|(_, s)| **s == State::Tank)
+            .map(|(p, _)| *p)
+    }
+
+    /// One line per row, `#` for walls, `.` for open cells, `O` for the oxygen system,
+    /// `S` for the starting cell and a space for unexplored cells
+    pub fn to_text(&self) -> String {
+        let bounds = match self.known.bounds() {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+        let mut text = String::new();
+        for y in bounds.min.y..=bounds.max.y {
+            for x in bounds.min.x..=bounds.max.x {
+                let point = Point { x, y };
+                text.push(match self.get(&point) {
+                    _ if point == Point::ORIGIN => 'S',
+                    Some(State::Wall) => '#',
+                    Some(State::Empty) => '.',
+                    Some(State::Tank) => 'O',
+                    None => ' ',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut cells = Vec::new();
+        let mut start = None;
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let point = Point::new(x as isize, y as isize);
+                let state = match c {
+                    '#' => State::Wall,
+                    '.' => State::Empty,
+                    'O' => State::Tank,
+                    'S' if start.is_none() => {
+                        start = Some(point);
+                        State::Empty
+                    }
+                    'S' => return Err("Several starting cells".to_owned()),
+                    ' ' => continue,
+                    c => return Err(format!("Invalid cell '{}' at ({}, {})", c, x, y)),
+                };
+                cells.push((point, state));
+            }
+        }
+        let start = start.ok_or_else(|| "No starting cell".to_owned())?;
+        let known = cells
+            .into_iter()
+            .map(|(p, state)| (Point::new(p.x - start.x, p.y - start.y), state))
+            .collect();
+        Ok(Maze { known })
+    }
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_text(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Number of moves between two open cells, walking only through explored cells
+    pub fn route_length(&self, from: Point, to: Point) -> Option<usize> {
+        pathfinding::a_star(self, from, to).map(|path| path.len())
+    }
+
+    /// Minutes needed for the oxygen to reach every open cell reachable from the system
+    pub fn oxygen_fill_time(&self) -> Option<usize> {
+        let tank = self.tank()?;
+        Some(oxygen::spread(self, &[tank], &HashSet::new()).fill_time())
+    }
+}
@@ -0,0 +1,400 @@
+use crate::grid::{Direction, Point, SparseGrid};
+use crate::intcode::{parse_intcode, IntcodeComputer, IntcodeState};
+use crate::render::{Cell, Renderer};
+use std::collections::HashSet;
+use std::fmt;
+
+mod maze;
+pub mod oxygen;
+pub mod pathfinding;
+mod strategy;
+pub use maze::Maze;
+pub use strategy::{DepthFirst, NearestFrontier, WallFollower};
+
+// Bounds the exploration in case a strategy keeps walking without discovering anything
+const MAX_MOVES: usize = 1_000_000;
+
+#[aoc(day15, part1)]
+fn how_far_is_system(robot_code: &[i64]) -> Result<usize, ExploreError> {
+    let computer = IntcodeComputer::new(Vec::from(robot_code));
+    let mut robot = Robot::new(computer);
+    let distance = robot.distance_to_tank(robot_code);
+    robot.or_draw(distance)
+}
+/// Lets the droid explore every reachable cell
+pub fn explore_maze(robot_code: &[i64]) -> Result<Maze, ExploreError> {
+    let computer = IntcodeComputer::new(Vec::from(robot_code));
+    let mut robot = Robot::new(computer);
+    let outcome = robot.run();
+    robot.or_draw(outcome)?;
+    Ok(robot.known)
+}
+
+#[aoc(day15, part2)]
+fn explore_all(robot_code: &[i64]) -> Result<usize, ExploreError> {
+    explore_maze(robot_code)?
+        .oxygen_fill_time()
+        .ok_or(ExploreError::NoOxygenSystem)
+}
+
+fn command(direction: Direction) -> i64 {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum State {
+    Wall,
+    Empty,
+    Tank,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExploreError {
+    InvalidStatus(i64),
+    /// The program stopped while the droid was moving
+    Halted,
+    /// The program asked for the next move without answering the previous one
+    NoStatus,
+    /// The droid hit a wall while walking through cells known to be open
+    UnexpectedWall(Point),
+    NoPath {
+        from: Point,
+        to: Point,
+    },
+    NoOxygenSystem,
+    TooManyMoves(usize),
+}
+
+impl fmt::Display for ExploreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExploreError::InvalidStatus(i) => write!(f, "Invalid status code: {}", i),
+            ExploreError::Halted => write!(f, "The droid program stopped"),
+            ExploreError::NoStatus => write!(f, "The droid did not report its last move"),
+            ExploreError::UnexpectedWall(p) => {
+                write!(f, "Hit a wall at ({}, {}) on an explored path", p.x, p.y)
+            }
+            ExploreError::NoPath { from, to } => write!(
+                f,
+                "No explored path from ({}, {}) to ({}, {})",
+                from.x, from.y, to.x, to.y
+            ),
+            ExploreError::NoOxygenSystem => write!(f, "No oxygen system found"),
+            ExploreError::TooManyMoves(moves) => write!(f, "Gave up after {} moves", moves),
+        }
+    }
+}
+
+impl std::error::Error for ExploreError {}
+
+/// How an exploration ended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Every cell reachable from the start was explored
+    Complete,
+    /// The strategy stopped with `unexplored` frontier cells left, and `unreachable` ones were
+    /// dropped because no explored path leads next to them
+    Incomplete {
+        unexplored: usize,
+        unreachable: usize,
+    },
+}
+
+/// How the droid picks the next unexplored cell once there are none around it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FrontierSelection {
+    /// Closest as the crow flies, even if walls are in the way
+    Manhattan,
+    /// Closest to walk to through explored cells
+    Walking,
+}
+
+/// Decides where the droid goes next
+pub trait Strategy {
+    fn name(&self) -> String;
+    /// Direction to try from `position`, `None` once the strategy has nothing left to explore.
+    /// `frontier` holds the unexplored cells next to explored open ones.
+    fn next_move(
+        &mut self,
+        maze: &Maze,
+        position: Point,
+        frontier: &HashSet<Point>,
+    ) -> Option<Direction>;
+}
+
+struct Robot {
+    logic: IntcodeComputer,
+    position: Point,
+    known: Maze,
+    boundry: HashSet<Point>,
+    unreachable: HashSet<Point>,
+    strategy: Box<dyn Strategy>,
+    moves: usize,
+}
+
+impl Robot {
+    fn new(computer: IntcodeComputer) -> Self {
+        let mut known = Maze::new(SparseGrid::new());
+        known.insert(Point::ORIGIN, State::Empty);
+        let boundry = Point::ORIGIN.neighbours().iter().copied().collect();
+        Self {
+            logic: computer,
+            position: Point::ORIGIN,
+            known,
+            boundry,
+            unreachable: HashSet::new(),
+            strategy: Box::new(NearestFrontier::new(FrontierSelection::Walking)),
+            moves: 0,
+        }
+    }
+    fn reinit_computer(&mut self, computer: IntcodeComputer) {
+        self.logic = computer;
+        self.position = Point::ORIGIN;
+    }
+    fn step(&mut self, direction: Direction) -> Result<State, ExploreError> {
+        self.moves += 1;
+        self.logic.add_input(command(direction));
+        loop {
+            match self.logic.step() {
+                IntcodeState::Ready => continue,
+                IntcodeState::Outputed => {
+                    return match *self.logic.output().last().unwrap() {
+                        0 => Ok(State::Wall),
+                        1 => Ok(State::Empty),
+                        2 => Ok(State::Tank),
+                        i => Err(ExploreError::InvalidStatus(i)),
+                    }
+                }
+                IntcodeState::Finished => return Err(ExploreError::Halted),
+                IntcodeState::NeedsInput => return Err(ExploreError::NoStatus),
+            }
+        }
+    }
+    fn step_and_update(&mut self, direction: Direction) -> Result<State, ExploreError> {
+        let state = self.step(direction)?;
+        let polled_position = self.position.step(direction);
+        self.boundry.remove(&polled_position);
+        self.known.insert(polled_position, state);
+        if state == State::Tank || state == State::Empty {
+            for new_neigbour in &polled_position.neighbours() {
+                if !self.known.contains(new_neigbour) {
+                    self.boundry.insert(*new_neigbour);
+                }
+            }
+            self.position = polled_position;
+        }
+        Ok(state)
+    }
+    fn step_on_empty(&mut self, direction: Direction) -> Result<(), ExploreError> {
+        let new_position = self.position.step(direction);
+        if self.step(direction)? == State::Wall {
+            return Err(ExploreError::UnexpectedWall(new_position));
+        }
+        self.position = new_position;
+        Ok(())
+    }
+    fn draw(&self) {
+        let wall = Cell::new(' ').plain('#').bg(yansi::Color::Red);
+        let tank = Cell::new('T').bold().underline();
+        let cells = self
+            .known
+            .iter()
+            .map(|(p, state)| {
+                let cell = match state {
+                    State::Wall => wall,
+                    State::Empty => Cell::new(' ').plain('.'),
+                    State::Tank => tank,
+                };
+                (*p, cell)
+            })
+            .collect();
+        let unknown = Cell::new(' ').plain('?').bg(yansi::Color::Cyan);
+        let droid = Cell::new('☺').plain('D').bold();
+        let renderer = Renderer::new()
+            .margin(1)
+            .background(unknown)
+            .highlight(self.position, droid)
+            .legend(droid, "droid")
+            .legend(wall, "wall")
+            .legend(tank, "oxygen system")
+            .legend(unknown, "unexplored");
+        print!("{}", renderer.render(&cells));
+    }
+    /// Tries one move, `None` once there is nothing left the strategy can explore
+    fn explore(&mut self) -> Result<Option<State>, ExploreError> {
+        if self.moves >= MAX_MOVES {
+            return Err(ExploreError::TooManyMoves(self.moves));
+        }
+        // Each pass drops at least one frontier cell, so this ends
+        while !self.boundry.is_empty() {
+            let next = self
+                .strategy
+                .next_move(&self.known, self.position, &self.boundry);
+            if let Some(direction) = next {
+                return self.step_and_update(direction).map(Some);
+            }
+            if self.prune_unreachable() == 0 {
+                break;
+            }
+        }
+        Ok(None)
+    }
+    /// Drops the frontier cells the droid cannot get next to through explored cells
+    fn prune_unreachable(&mut self) -> usize {
+        let distances = pathfinding::dijkstra(&self.known, self.position);
+        let unreachable: Vec<Point> = self
+            .boundry
+            .iter()
+            .filter(|p| {
+                p.neighbours()
+                    .iter()
+                    .all(|n| distances.distance(n).is_none())
+            })
+            .copied()
+            .collect();
+        for point in &unreachable {
+            self.boundry.remove(point);
+            self.unreachable.insert(*point);
+        }
+        unreachable.len()
+    }
+    fn run(&mut self) -> Result<Outcome, ExploreError> {
+        while self.explore()?.is_some() {}
+        if self.boundry.is_empty() && self.unreachable.is_empty() {
+            Ok(Outcome::Complete)
+        } else {
+            Ok(Outcome::Incomplete {
+                unexplored: self.boundry.len(),
+                unreachable: self.unreachable.len(),
+            })
+        }
+    }
+    fn distance_to_tank(&mut self, robot_code: &[i64]) -> Result<usize, ExploreError> {
+        loop {
+            match self.explore()? {
+                Some(State::Tank) => break,
+                Some(_) => continue,
+                None => return Err(ExploreError::NoOxygenSystem),
+            }
+        }
+        let tank = self.position;
+        self.reinit_computer(IntcodeComputer::new(Vec::from(robot_code)));
+        self.go_to_by_explored(tank)
+    }
+    fn walk(&mut self, path: &[Direction]) -> Result<(), ExploreError> {
+        for &direction in path {
+            self.step_on_empty(direction)?;
+        }
+        Ok(())
+    }
+    fn go_to_by_explored(&mut self, goal: Point) -> Result<usize, ExploreError> {
+        let path =
+            pathfinding::a_star(&self.known, self.position, goal).ok_or(ExploreError::NoPath {
+                from: self.position,
+                to: goal,
+            })?;
+        self.walk(&path)?;
+        Ok(path.len())
+    }
+    /// Draws what was explored when something went wrong
+    fn or_draw<T>(&self, result: Result<T, ExploreError>) -> Result<T, ExploreError> {
+        if result.is_err() {
+            self.draw();
+        }
+        result
+    }
+}
+
+/// How well a strategy did at mapping the whole area
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExplorationReport {
+    pub strategy: String,
+    pub moves: usize,
+    pub cells_mapped: usize,
+    pub outcome: Outcome,
+}
+
+impl fmt::Display for ExplorationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<30} {:>6} moves {:>6} cells",
+            self.strategy, self.moves, self.cells_mapped
+        )?;
+        match self.outcome {
+            Outcome::Complete => Ok(()),
+            Outcome::Incomplete {
+                unexplored,
+                unreachable,
+            } => write!(
+                f,
+                " (incomplete: {} unexplored, {} unreachable)",
+                unexplored, unreachable
+            ),
+        }
+    }
+}
+
+pub fn explore_with(
+    robot_code: &[i64],
+    strategy: Box<dyn Strategy>,
+) -> Result<ExplorationReport, ExploreError> {
+    let computer = IntcodeComputer::new(Vec::from(robot_code));
+    let mut robot = Robot::new(computer);
+    robot.strategy = strategy;
+    let outcome = robot.run()?;
+    Ok(ExplorationReport {
+        strategy: robot.strategy.name(),
+        moves: robot.moves,
+        cells_mapped: robot.known.iter().count(),
+        outcome,
+    })
+}
+
+pub fn compare_strategies(robot_code: &[i64]) -> Result<Vec<ExplorationReport>, ExploreError> {
+    let strategies: Vec<Box<dyn Strategy>> = vec![
+        Box::new(DepthFirst::new()),
+        Box::new(WallFollower::new()),
+        Box::new(NearestFrontier::new(FrontierSelection::Manhattan)),
+        Box::new(NearestFrontier::new(FrontierSelection::Walking)),
+    ];
+    strategies
+        .into_iter()
+        .map(|strategy| explore_with(robot_code, strategy))
+        .collect()
+}
+
+#[aoc(day15, part2, strategies)]
+fn explore_all_strategies(robot_code: &[i64]) -> Result<usize, ExploreError> {
+    for report in compare_strategies(robot_code)? {
+        println!("{}", report);
+    }
+    explore_all(robot_code)
+}
+
+/// Droid moves needed to explore the whole area with each frontier selection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelectionStats {
+    pub manhattan_moves: usize,
+    pub walking_moves: usize,
+}
+
+impl SelectionStats {
+    pub fn moves_saved(&self) -> isize {
+        self.manhattan_moves as isize - self.walking_moves as isize
+    }
+}
+
+pub fn compare_frontier_selection(robot_code: &[i64]) -> Result<SelectionStats, ExploreError> {
+    let moves = |selection| {
+        explore_with(robot_code, Box::new(NearestFrontier::new(selection))).map(|r| r.moves)
+    };
+    Ok(SelectionStats {
+        manhattan_moves: moves(FrontierSelection::Manhattan)?,
+        walking_moves: moves(FrontierSelection::Walking)?,
+    })
+}
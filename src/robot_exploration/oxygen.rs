@@ -0,0 +1,116 @@
+use super::{Maze, State};
+use crate::grid::{Point, SparseGrid};
+use crate::render::{Cell, Renderer};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// How oxygen spread through the maze, one step to the open neighbours every minute
+#[derive(Debug, Clone)]
+pub struct Diffusion {
+    // cells reached during each minute, the sources being reached at minute 0
+    frontiers: Vec<Vec<Point>>,
+    reached: HashMap<Point, usize>,
+    blocked: HashSet<Point>,
+}
+
+/// Spreads oxygen from every source at once, never entering the blocked cells.
+/// Sources which are walls, unexplored or blocked are ignored.
+pub fn spread(maze: &Maze, sources: &[Point], blocked: &HashSet<Point>) -> Diffusion {
+    let mut reached = HashMap::new();
+    let mut current: Vec<Point> = sources
+        .iter()
+        .copied()
+        .filter(|p| maze.is_open(p) && !blocked.contains(p) && reached.insert(*p, 0).is_none())
+        .collect();
+    let mut frontiers = Vec::new();
+    while !current.is_empty() {
+        let minute = frontiers.len() + 1;
+        let mut next = Vec::new();
+        for point in &current {
+            for neighbour in &point.neighbours() {
+                if maze.is_open(neighbour)
+                    && !blocked.contains(neighbour)
+                    && !reached.contains_key(neighbour)
+                {
+                    reached.insert(*neighbour, minute);
+                    next.push(*neighbour);
+                }
+            }
+        }
+        frontiers.push(current);
+        current = next;
+    }
+    Diffusion {
+        frontiers,
+        reached,
+        blocked: blocked.clone(),
+    }
+}
+
+impl Diffusion {
+    /// Minutes until the oxygen stops spreading
+    pub fn fill_time(&self) -> usize {
+        self.frontiers.len().saturating_sub(1)
+    }
+    /// Cells the oxygen reached during `minute`
+    pub fn frontier(&self, minute: usize) -> &[Point] {
+        self.frontiers.get(minute).map_or(&[], |f| f.as_slice())
+    }
+    pub fn frontiers(&self) -> impl Iterator<Item = &[Point]> {
+        self.frontiers.iter().map(|f| f.as_slice())
+    }
+    /// Minute at which the oxygen reached `point`
+    pub fn reached_at(&self, point: &Point) -> Option<usize> {
+        self.reached.get(point).copied()
+    }
+    /// One of the last cells to be filled, with the minute it was
+    pub fn farthest(&self) -> Option<(Point, usize)> {
+        self.frontiers
+            .last()
+            .and_then(|f| f.iter().min())
+            .map(|p| (*p, self.fill_time()))
+    }
+    /// Open cells of the maze the oxygen never reaches
+    pub fn unreached(&self, maze: &Maze) -> Vec<Point> {
+        maze.iter()
+            .filter(|(p, s)| **s != State::Wall && !self.reached.contains_key(p))
+            .map(|(p, _)| *p)
+            .collect()
+    }
+
+    /// The maze once `minute` has passed, the cells filled during that minute standing out
+    pub fn render(&self, maze: &Maze, minute: usize) -> String {
+        let wall = Cell::new(' ').plain('#').bg(yansi::Color::Red);
+        let oxygen = Cell::new(' ').plain('o').bg(yansi::Color::Blue);
+        let newest = Cell::new(' ').plain('O').bg(yansi::Color::Cyan);
+        let blocked = Cell::new('X').fg(yansi::Color::Yellow).bold();
+        let cells: SparseGrid<Cell> = maze
+            .iter()
+            .filter_map(|(p, state)| {
+                let cell = match (state, self.reached_at(p)) {
+                    _ if self.blocked.contains(p) => blocked,
+                    (State::Wall, _) => wall,
+                    (_, Some(m)) if m == minute => newest,
+                    (_, Some(m)) if m < minute => oxygen,
+                    _ => return None,
+                };
+                Some((*p, cell))
+            })
+            .collect();
+        Renderer::new()
+            .margin(1)
+            .legend(wall, "wall")
+            .legend(oxygen, "oxygen")
+            .legend(newest, "spread this minute")
+            .legend(blocked, "blocked")
+            .render(&cells)
+    }
+    /// Draws every minute in the terminal, waiting `delay` between frames
+    pub fn animate(&self, maze: &Maze, delay: Duration) {
+        for minute in 0..self.frontiers.len() {
+            print!("\x1b[2J\x1b[H{}", self.render(maze, minute));
+            println!("minute {}/{}", minute, self.fill_time());
+            std::thread::sleep(delay);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::Maze;
+use crate::grid::{Direction, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Shortest distances from a source, with the cell each one is reached from
+pub struct Distances {
+    source: Point,
+    distance: HashMap<Point, usize>,
+    predecessor: HashMap<Point, Point>,
+}
+
+impl Distances {
+    pub fn source(&self) -> Point {
+        self.source
+    }
+    pub fn distance(&self, to: &Point) -> Option<usize> {
+        self.distance.get(to).copied()
+    }
+    pub fn reachable(&self) -> impl Iterator<Item = (&Point, &usize)> {
+        self.distance.iter()
+    }
+    /// Moves leading from the source to `to`
+    pub fn path_to(&self, to: Point) -> Option<Vec<Direction>> {
+        if !self.distance.contains_key(&to) {
+            return None;
+        }
+        Some(walk_back(&self.predecessor, self.source, to))
+    }
+}
+
+fn walk_back(predecessor: &HashMap<Point, Point>, from: Point, to: Point) -> Vec<Direction> {
+    let mut path = Vec::new();
+    let mut current = to;
+    while current != from {
+        let previous = predecessor[&current];
+        path.push(previous.direction_to(&current).unwrap());
+        current = previous;
+    }
+    path.reverse();
+    path
+}
+
+/// Distances from `from` to every open cell it can reach through explored cells
+pub fn dijkstra(maze: &Maze, from: Point) -> Distances {
+    let mut distance = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    distance.insert(from, 0);
+    queue.push(Reverse((0, from)));
+    while let Some(Reverse((cost, point))) = queue.pop() {
+        if cost > distance[&point] {
+            continue;
+        }
+        for neighbour in &point.neighbours() {
+            if !maze.is_open(neighbour) {
+                continue;
+            }
+            let new_cost = cost + 1;
+            if distance.get(neighbour).is_none_or(|&d| new_cost < d) {
+                distance.insert(*neighbour, new_cost);
+                predecessor.insert(*neighbour, point);
+                queue.push(Reverse((new_cost, *neighbour)));
+            }
+        }
+    }
+    Distances {
+        source: from,
+        distance,
+        predecessor,
+    }
+}
+
+/// Shortest path between two open cells, guided by the Manhattan distance to the goal
+pub fn a_star(maze: &Maze, from: Point, to: Point) -> Option<Vec<Direction>> {
+    let heuristic = |p: &Point| p.manhattan(&to) as usize;
+    let mut cost = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    cost.insert(from, 0);
+    queue.push(Reverse((heuristic(&from), 0, from)));
+    while let Some(Reverse((_, current_cost, point))) = queue.pop() {
+        if point == to {
+            return Some(walk_back(&predecessor, from, to));
+        }
+        if current_cost > cost[&point] {
+            continue;
+        }
+        for neighbour in &point.neighbours() {
+            if !maze.is_open(neighbour) {
+                continue;
+            }
+            let new_cost = current_cost + 1;
+            if cost.get(neighbour).is_none_or(|&c| new_cost < c) {
+                cost.insert(*neighbour, new_cost);
+                predecessor.insert(*neighbour, point);
+                queue.push(Reverse((
+                    new_cost + heuristic(neighbour),
+                    new_cost,
+                    *neighbour,
+                )));
+            }
+        }
+    }
+    None
+}
+
+/// Unexplored cell with the shortest walk from `from`, with the path to the open cell next to it
+pub fn nearest_frontier<'a, I>(
+    maze: &Maze,
+    from: Point,
+    frontier: I,
+) -> Option<(Point, Vec<Direction>)>
+where
+    I: IntoIterator<Item = &'a Point>,
+{
+    let distances = dijkstra(maze, from);
+    frontier
+        .into_iter()
+        .filter_map(|unknown| {
+            unknown
+                .neighbours()
+                .iter()
+                .filter_map(|n| distances.distance(n).map(|d| (d, *n)))
+                .min()
+                .map(|(d, next_to)| (d, *unknown, next_to))
+        })
+        .min()
+        .map(|(_, unknown, next_to)| (unknown, distances.path_to(next_to).unwrap()))
+}
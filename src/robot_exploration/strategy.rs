@@ -0,0 +1,156 @@
+use super::{pathfinding, FrontierSelection, Maze, State, Strategy};
+use crate::grid::{Direction, Point};
+use std::collections::{HashSet, VecDeque};
+
+fn unknown_neighbour(maze: &Maze, position: Point) -> Option<Direction> {
+    Direction::ALL
+        .iter()
+        .copied()
+        .find(|&d| !maze.contains(&position.step(d)))
+}
+
+/// Walks to the closest unexplored cell once there are none around the droid
+#[derive(Debug)]
+pub struct NearestFrontier {
+    selection: FrontierSelection,
+    planned: VecDeque<Direction>,
+}
+
+impl NearestFrontier {
+    pub fn new(selection: FrontierSelection) -> Self {
+        NearestFrontier {
+            selection,
+            planned: VecDeque::new(),
+        }
+    }
+
+    fn plan(
+        &self,
+        maze: &Maze,
+        position: Point,
+        frontier: &HashSet<Point>,
+    ) -> Option<Vec<Direction>> {
+        match self.selection {
+            FrontierSelection::Manhattan => {
+                let point = frontier.iter().min_by_key(|p| position.manhattan(p))?;
+                point
+                    .neighbours()
+                    .iter()
+                    .find(|n| maze.is_open(n))
+                    .and_then(|n| pathfinding::a_star(maze, position, *n))
+            }
+            FrontierSelection::Walking => {
+                pathfinding::nearest_frontier(maze, position, frontier).map(|(_, path)| path)
+            }
+        }
+    }
+}
+
+impl Strategy for NearestFrontier {
+    fn name(&self) -> String {
+        match self.selection {
+            FrontierSelection::Manhattan => "nearest frontier (manhattan)".to_owned(),
+            FrontierSelection::Walking => "nearest frontier (walking)".to_owned(),
+        }
+    }
+    fn next_move(
+        &mut self,
+        maze: &Maze,
+        position: Point,
+        frontier: &HashSet<Point>,
+    ) -> Option<Direction> {
+        if let Some(direction) = self.planned.pop_front() {
+            return Some(direction);
+        }
+        if let Some(direction) = unknown_neighbour(maze, position) {
+            return Some(direction);
+        }
+        self.planned = self.plan(maze, position, frontier)?.into();
+        self.planned.pop_front()
+    }
+}
+
+/// Goes as deep as possible, then walks back its own trail to the last cell with unexplored
+/// neighbours
+#[derive(Debug, Default)]
+pub struct DepthFirst {
+    trail: Vec<Direction>,
+    probe: Option<(Point, Direction)>,
+}
+
+impl DepthFirst {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Strategy for DepthFirst {
+    fn name(&self) -> String {
+        "depth first".to_owned()
+    }
+    fn next_move(&mut self, maze: &Maze, position: Point, _: &HashSet<Point>) -> Option<Direction> {
+        if let Some((from, direction)) = self.probe.take() {
+            if from != position {
+                self.trail.push(direction);
+            }
+        }
+        if let Some(direction) = unknown_neighbour(maze, position) {
+            self.probe = Some((position, direction));
+            return Some(direction);
+        }
+        self.trail.pop().map(Direction::reverse)
+    }
+}
+
+/// Keeps a hand on the wall to its left, stopping once it is back where it already was facing
+/// the same way. Open areas and walls not connected to the start are never mapped.
+#[derive(Debug)]
+pub struct WallFollower {
+    heading: Direction,
+    probe: Option<(Point, Direction)>,
+    seen: HashSet<(Point, Direction)>,
+}
+
+impl Default for WallFollower {
+    fn default() -> Self {
+        WallFollower {
+            heading: Direction::Up,
+            probe: None,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl WallFollower {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Strategy for WallFollower {
+    fn name(&self) -> String {
+        "left-hand wall following".to_owned()
+    }
+    fn next_move(&mut self, maze: &Maze, position: Point, _: &HashSet<Point>) -> Option<Direction> {
+        if let Some((from, direction)) = self.probe.take() {
+            if from != position {
+                self.heading = direction;
+                if !self.seen.insert((position, direction)) {
+                    return None;
+                }
+            }
+        }
+        let heading = self.heading;
+        let direction = [
+            heading.turn_left(),
+            heading,
+            heading.turn_right(),
+            heading.reverse(),
+        ]
+        .iter()
+        .copied()
+        .find(|&d| maze.get(&position.step(d)) != Some(State::Wall))?;
+        self.probe = Some((position, direction));
+        Some(direction)
+    }
+}
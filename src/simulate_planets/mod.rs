@@ -0,0 +1,126 @@
+mod nbody;
+pub use nbody::{Body, ForceLaw, InverseSquare, Scalar, Simulation, UnitGravity};
+
+/// A moon of Jupiter
+pub type State = Body<isize, 3>;
+
+#[aoc_generator(day12)]
+pub fn parse_input(input: &str) -> Vec<State> {
+    input
+        .lines()
+        .map(|line| {
+            let x_start = line.find("x").unwrap();
+            let y_start = line.find("y").unwrap();
+            let z_start = line.find("z").unwrap();
+
+            let x_comma = line[x_start..].find(",").unwrap();
+            let y_comma = line[y_start..].find(",").unwrap();
+
+            let x = line[(x_start + 2)..=x_comma].parse().unwrap();
+            let y = line[(y_start + 2)..(y_start + y_comma)].parse().unwrap();
+            let z = line[(z_start + 2)..(line.len() - 1)].parse().unwrap();
+            State::at([x, y, z])
+        })
+        .collect()
+}
+
+#[aoc(day12, part1)]
+pub fn disp(positions: &[State]) -> usize {
+    let mut moons = Simulation::new(positions.to_vec(), UnitGravity);
+    moons.run(1000);
+    moons.total_energy() as usize
+}
+
+fn update_sequence<const D: usize>(
+    seq: &mut [Vec<Vec<isize>>],
+    states: &[Body<isize, D>],
+    seq_length: &[Vec<Option<usize>>],
+) {
+    for ((s, seq), len) in states.iter().zip(seq.iter_mut()).zip(seq_length.iter()) {
+        for (axis, _) in len.iter().enumerate().filter(|(_, len)| len.is_none()) {
+            seq[axis].push(s.position[axis]);
+        }
+    }
+}
+
+fn check_seqs(seq: &[Vec<Vec<isize>>], seq_length: &mut [Vec<Option<usize>>]) {
+    for (seqs, lens) in seq.iter().zip(seq_length.iter_mut()) {
+        for (seq, len) in seqs
+            .iter()
+            .zip(lens.iter_mut())
+            .filter(|(_, len)| len.is_none())
+        {
+            let half_length = seq.len() / 2;
+            if seq[..half_length] == seq[half_length..] {
+                *len = Some(half_length);
+            }
+        }
+    }
+}
+
+fn has_all_seqs(seq_length: &[Vec<Option<usize>>]) -> bool {
+    seq_length.iter().all(|p| p.iter().all(|c| c.is_some()))
+}
+
+use num::Integer;
+
+fn lcm(numbers: &[usize]) -> usize {
+    if numbers.len() == 1 {
+        numbers[0]
+    } else if numbers.len() == 2 {
+        numbers[0].lcm(&numbers[1])
+    } else {
+        numbers[0].lcm(&lcm(&numbers[1..]))
+    }
+}
+
+/// Steps until every coordinate of every body has started repeating, each axis being
+/// independent from the others
+fn period<const D: usize>(bodies: &[Body<isize, D>]) -> usize {
+    let mut sequence = vec![vec![Vec::new(); D]; bodies.len()];
+    let mut seq_length = vec![vec![None; D]; bodies.len()];
+    let mut moons = Simulation::new(bodies.to_vec(), UnitGravity);
+    moons.run_until(usize::MAX, |moons| {
+        update_sequence(&mut sequence, moons.bodies(), &seq_length);
+        // Look for sequences repeated at least twice, once every two steps
+        if moons.steps() >= 5 && moons.steps() % 2 == 1 {
+            check_seqs(&sequence, &mut seq_length);
+        }
+        has_all_seqs(&seq_length)
+    });
+    let flat_seq: Vec<_> = seq_length
+        .into_iter()
+        .flat_map(|p| p.into_iter().map(|c| c.unwrap()))
+        .collect();
+    lcm(&flat_seq)
+}
+
+#[aoc(day12, part2)]
+pub fn find_repeat(positions: &[State]) -> usize {
+    period(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_examples() {
+        let first = "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=4, y=-8, z=8>\n<x=3, y=5, z=-1>";
+        assert_eq!(find_repeat(&parse_input(first)), 2772);
+        let second = "<x=-8, y=-10, z=0>\n<x=5, y=5, z=10>\n<x=2, y=-7, z=3>\n<x=9, y=-8, z=-3>";
+        assert_eq!(find_repeat(&parse_input(second)), 4_686_774_924);
+    }
+
+    #[test]
+    fn repeats_in_other_dimensions() {
+        // the first example repeats after 18 steps along x and 28 along y
+        let x: Vec<_> = [-1, 2, 4, 3].iter().map(|&x| Body::at([x])).collect();
+        assert_eq!(period(&x), 18);
+        let xy: Vec<_> = [[-1, 0], [2, -10], [4, -8], [3, 5]]
+            .iter()
+            .map(|&p| Body::at(p))
+            .collect();
+        assert_eq!(period(&xy), 252);
+    }
+}
@@ -0,0 +1,286 @@
+use num::{Float, Signed};
+use std::ops::AddAssign;
+
+/// Numbers the simulation works with, integers for the moons and floats for real gravity
+pub trait Scalar: Signed + Copy + PartialOrd + AddAssign {}
+
+impl<T: Signed + Copy + PartialOrd + AddAssign> Scalar for T {}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Body<T, const D: usize> {
+    pub position: [T; D],
+    pub velocity: [T; D],
+    pub mass: T,
+}
+
+impl<T: Scalar, const D: usize> Body<T, D> {
+    /// Unit mass body standing still at `position`
+    pub fn at(position: [T; D]) -> Self {
+        Body {
+            position,
+            velocity: [T::zero(); D],
+            mass: T::one(),
+        }
+    }
+    pub fn with_velocity(mut self, velocity: [T; D]) -> Self {
+        self.velocity = velocity;
+        self
+    }
+    pub fn with_mass(mut self, mass: T) -> Self {
+        self.mass = mass;
+        self
+    }
+}
+
+/// How bodies pull each other, and the energy this gives them
+pub trait ForceLaw<T, const D: usize> {
+    /// Acceleration of `body` towards `other`
+    fn acceleration(&self, body: &Body<T, D>, other: &Body<T, D>) -> [T; D];
+    fn kinetic(&self, body: &Body<T, D>) -> T;
+    /// Share of the potential energy of `bodies[i]`, each body appearing in `bodies`
+    fn potential(&self, i: usize, bodies: &[Body<T, D>]) -> T;
+    fn energy(&self, kinetic: T, potential: T) -> T;
+}
+
+/// Each coordinate moves by one towards the other body, the energy being the product of the
+/// manhattan norms of the velocity and position
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnitGravity;
+
+fn manhattan<T: Scalar>(vector: &[T]) -> T {
+    vector.iter().fold(T::zero(), |sum, c| sum + c.abs())
+}
+
+impl<T: Scalar, const D: usize> ForceLaw<T, D> for UnitGravity {
+    fn acceleration(&self, body: &Body<T, D>, other: &Body<T, D>) -> [T; D] {
+        let mut acceleration = [T::zero(); D];
+        for (a, (x, o)) in acceleration
+            .iter_mut()
+            .zip(body.position.iter().zip(&other.position))
+        {
+            if o > x {
+                *a = T::one();
+            } else if o < x {
+                *a = -T::one();
+            }
+        }
+        acceleration
+    }
+    fn kinetic(&self, body: &Body<T, D>) -> T {
+        manhattan(&body.velocity)
+    }
+    fn potential(&self, i: usize, bodies: &[Body<T, D>]) -> T {
+        manhattan(&bodies[i].position)
+    }
+    fn energy(&self, kinetic: T, potential: T) -> T {
+        kinetic * potential
+    }
+}
+
+/// Newton's gravity, `softening` keeping the pull finite when bodies get very close
+#[derive(Debug, Clone, Copy)]
+pub struct InverseSquare<T> {
+    pub gravity: T,
+    pub softening: T,
+}
+
+impl<T: Float> InverseSquare<T> {
+    fn distance<const D: usize>(&self, from: &[T; D], to: &[T; D]) -> T {
+        let squared = from
+            .iter()
+            .zip(to)
+            .fold(T::zero(), |sum, (&a, &b)| sum + (b - a) * (b - a));
+        (squared + self.softening * self.softening).sqrt()
+    }
+}
+
+impl<T: Scalar + Float, const D: usize> ForceLaw<T, D> for InverseSquare<T> {
+    fn acceleration(&self, body: &Body<T, D>, other: &Body<T, D>) -> [T; D] {
+        let distance = self.distance(&body.position, &other.position);
+        let mut acceleration = [T::zero(); D];
+        if distance == T::zero() {
+            return acceleration;
+        }
+        let pull = self.gravity * other.mass / (distance * distance * distance);
+        for (a, (&x, &o)) in acceleration
+            .iter_mut()
+            .zip(body.position.iter().zip(&other.position))
+        {
+            *a = pull * (o - x);
+        }
+        acceleration
+    }
+    fn kinetic(&self, body: &Body<T, D>) -> T {
+        let squared = body.velocity.iter().fold(T::zero(), |sum, &v| sum + v * v);
+        body.mass * squared / (T::one() + T::one())
+    }
+    fn potential(&self, i: usize, bodies: &[Body<T, D>]) -> T {
+        let body = &bodies[i];
+        // each pair shares its energy between the two bodies
+        let pairs = bodies
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, other)| {
+                let distance = self.distance(&body.position, &other.position);
+                if distance == T::zero() {
+                    T::zero()
+                } else {
+                    -self.gravity * body.mass * other.mass / distance
+                }
+            })
+            .fold(T::zero(), |sum, e| sum + e);
+        pairs / (T::one() + T::one())
+    }
+    fn energy(&self, kinetic: T, potential: T) -> T {
+        kinetic + potential
+    }
+}
+
+/// Bodies moving in `D` dimensions under the force law `L`
+#[derive(Debug, Clone)]
+pub struct Simulation<T, L, const D: usize> {
+    bodies: Vec<Body<T, D>>,
+    law: L,
+    time_step: T,
+    steps: usize,
+}
+
+impl<T: Scalar, L: ForceLaw<T, D>, const D: usize> Simulation<T, L, D> {
+    pub fn new(bodies: Vec<Body<T, D>>, law: L) -> Self {
+        Simulation {
+            bodies,
+            law,
+            time_step: T::one(),
+            steps: 0,
+        }
+    }
+    pub fn with_time_step(mut self, time_step: T) -> Self {
+        self.time_step = time_step;
+        self
+    }
+    pub fn bodies(&self) -> &[Body<T, D>] {
+        &self.bodies
+    }
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Updates all velocities from the current positions, then moves the bodies
+    pub fn step(&mut self) {
+        let dt = self.time_step;
+        for i in 0..self.bodies.len() {
+            let mut velocity = self.bodies[i].velocity;
+            for j in (0..self.bodies.len()).filter(|&j| j != i) {
+                let acceleration = self.law.acceleration(&self.bodies[i], &self.bodies[j]);
+                for (v, a) in velocity.iter_mut().zip(&acceleration) {
+                    *v += *a * dt;
+                }
+            }
+            self.bodies[i].velocity = velocity;
+        }
+        for body in &mut self.bodies {
+            for (x, v) in body.position.iter_mut().zip(&body.velocity) {
+                *x += *v * dt;
+            }
+        }
+        self.steps += 1;
+    }
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+    /// Steps until `stop` holds, giving the number of steps taken, or `None` if it still
+    /// doesn't after `max_steps`
+    pub fn run_until<F: FnMut(&Self) -> bool>(
+        &mut self,
+        max_steps: usize,
+        mut stop: F,
+    ) -> Option<usize> {
+        let start = self.steps;
+        loop {
+            if stop(self) {
+                return Some(self.steps - start);
+            }
+            if self.steps - start == max_steps {
+                return None;
+            }
+            self.step();
+        }
+    }
+
+    pub fn kinetic(&self, i: usize) -> T {
+        self.law.kinetic(&self.bodies[i])
+    }
+    pub fn potential(&self, i: usize) -> T {
+        self.law.potential(i, &self.bodies)
+    }
+    pub fn energy(&self, i: usize) -> T {
+        self.law.energy(self.kinetic(i), self.potential(i))
+    }
+    pub fn total_energy(&self) -> T {
+        (0..self.bodies.len()).fold(T::zero(), |sum, i| sum + self.energy(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // First example of day 12
+    fn moons() -> Simulation<isize, UnitGravity, 3> {
+        let positions = [[-1, 0, 2], [2, -10, -7], [4, -8, 8], [3, 5, -1]];
+        Simulation::new(
+            positions.iter().map(|&p| Body::at(p)).collect(),
+            UnitGravity,
+        )
+    }
+
+    #[test]
+    fn unit_gravity_energy() {
+        let mut moons = moons();
+        moons.run(10);
+        assert_eq!(moons.bodies()[0].position, [2, 1, -3]);
+        assert_eq!(moons.bodies()[0].velocity, [-3, -2, 1]);
+        assert_eq!(moons.total_energy(), 179);
+    }
+
+    #[test]
+    fn run_until_stops() {
+        let mut moons = moons();
+        assert_eq!(moons.run_until(10, |_| true), Some(0));
+        moons.run(3);
+        assert_eq!(moons.run_until(10, |moons| moons.steps() == 5), Some(2));
+        assert_eq!(moons.steps(), 5);
+    }
+
+    #[test]
+    fn run_until_gives_up() {
+        let mut moons = moons();
+        assert_eq!(moons.run_until(4, |_| false), None);
+        assert_eq!(moons.steps(), 4);
+    }
+
+    #[test]
+    fn inverse_square_conserves_energy() {
+        // two equal bodies on a circular orbit around their centre
+        let bodies = vec![
+            Body::at([1.0, 0.0]).with_velocity([0.0, 0.5]),
+            Body::at([-1.0, 0.0]).with_velocity([0.0, -0.5]),
+        ];
+        let law = InverseSquare {
+            gravity: 1.0,
+            softening: 0.0,
+        };
+        let mut orbit = Simulation::new(bodies, law).with_time_step(0.001);
+        let start = orbit.total_energy();
+        assert!((start + 0.25f64).abs() < 1e-12);
+        // a bit more than a full turn
+        orbit.run(15_000);
+        assert!((orbit.total_energy() - start).abs() < 1e-3 * start.abs());
+        let [a, b] = [orbit.bodies()[0].position, orbit.bodies()[1].position];
+        let separation = ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt();
+        assert!((separation - 2.0).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,84 @@
+use super::Scanner;
+
+// Steepest edge looked for, in columns per row
+const MAX_SLOPE: usize = 10;
+// Widest beam followed before deciding the drone program is broken
+const MAX_WIDTH: usize = 100_000;
+
+/// Last column of the beam on row `y`, walking right from `x` which is in the beam
+pub(super) fn right_edge_from(x: usize, y: usize, scanner: &mut Scanner) -> Result<usize, String> {
+    let mut right = x;
+    while scanner.scan(right + 1, y) {
+        right += 1;
+        if right - x > MAX_WIDTH {
+            return Err(format!("Beam wider than {} on row {}", MAX_WIDTH, y));
+        }
+    }
+    Ok(right)
+}
+
+/// Edges of row `y` without knowing anything about the beam, scanning the row in chunks of
+/// `y + 1` columns until the beam is found
+pub(super) fn scan_row(y: usize, scanner: &mut Scanner) -> Result<Option<(usize, usize)>, String> {
+    let mut start = 0;
+    let left = loop {
+        if start > MAX_SLOPE * y + 1 {
+            return Ok(None);
+        }
+        let chunk: Vec<_> = (start..=start + y).map(|x| (x, y)).collect();
+        if let Some(i) = scanner.scan_many(&chunk).into_iter().position(|p| p) {
+            break start + i;
+        }
+        start += y + 1;
+    };
+    Ok(Some((left, right_edge_from(left, y, scanner)?)))
+}
+
+/// Follows the beam edges going down, each row being searched from the edges of the last row
+/// where the beam was seen
+#[derive(Debug, Clone, Default)]
+pub struct EdgeTracker {
+    // row, left edge, right edge
+    last: Option<(usize, usize, usize)>,
+}
+
+impl EdgeTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Edges of row `y`, `None` if the beam misses it. Rows can be skipped but not revisited.
+    pub fn edges(
+        &mut self,
+        y: usize,
+        scanner: &mut Scanner,
+    ) -> Result<Option<(usize, usize)>, String> {
+        let (row, last_left, last_right) = match self.last {
+            None => {
+                let edges = scan_row(y, scanner)?;
+                self.last = edges.map(|(left, right)| (y, left, right));
+                return Ok(edges);
+            }
+            Some(last) => last,
+        };
+        if y < row {
+            return Err(format!("Row {} is above the last tracked row {}", y, row));
+        }
+        // Neither edge can have moved by more than the steepest slope
+        let furthest = last_right + (y - row) * MAX_SLOPE + 1;
+        let mut left = match (last_left..=furthest).find(|&x| scanner.scan(x, y)) {
+            Some(left) => left,
+            None => return Ok(None),
+        };
+        while left > 0 && scanner.scan(left - 1, y) {
+            left -= 1;
+        }
+        let start = if last_right > left && scanner.scan(last_right, y) {
+            last_right
+        } else {
+            left
+        };
+        let right = right_edge_from(start, y, scanner)?;
+        self.last = Some((y, left, right));
+        Ok(Some((left, right)))
+    }
+}
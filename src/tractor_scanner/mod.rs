@@ -0,0 +1,137 @@
+use crate::grid::Point;
+use crate::intcode::{parse_intcode, IntcodeComputer};
+use rayon::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+mod edges;
+mod model;
+mod view;
+pub use edges::EdgeTracker;
+pub use model::{find_square, BeamModel};
+pub use view::BeamView;
+
+// Rows followed before giving up on finding a square
+const MAX_ROWS: usize = 100_000;
+
+fn scan_point(x: usize, y: usize, code: &[i64]) -> bool {
+    let mut computer = IntcodeComputer::new(Vec::from(code));
+    computer.add_input(x as i64);
+    computer.add_input(y as i64);
+    computer.run();
+    *computer.output().last().unwrap() == 1
+}
+
+/// How often the drone program was run, and how often it could be avoided
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanStats {
+    pub cache_hits: usize,
+    pub intcode_runs: usize,
+}
+
+impl fmt::Display for ScanStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} Intcode runs, {} cache hits",
+            self.intcode_runs, self.cache_hits
+        )
+    }
+}
+
+/// Deploys drones, remembering where each one was pulled so no point is scanned twice
+pub struct Scanner<'a> {
+    code: &'a [i64],
+    cache: HashMap<(usize, usize), bool>,
+    stats: ScanStats,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(code: &'a [i64]) -> Self {
+        Scanner {
+            code,
+            cache: HashMap::new(),
+            stats: ScanStats::default(),
+        }
+    }
+    pub fn stats(&self) -> ScanStats {
+        self.stats
+    }
+    pub fn scan(&mut self, x: usize, y: usize) -> bool {
+        if let Some(&pulled) = self.cache.get(&(x, y)) {
+            self.stats.cache_hits += 1;
+            return pulled;
+        }
+        self.stats.intcode_runs += 1;
+        let pulled = scan_point(x, y, self.code);
+        self.cache.insert((x, y), pulled);
+        pulled
+    }
+    /// Scans every point, running the drone program for the new ones across threads
+    pub fn scan_many(&mut self, points: &[(usize, usize)]) -> Vec<bool> {
+        let mut new_points: Vec<_> = points
+            .iter()
+            .filter(|p| !self.cache.contains_key(p))
+            .copied()
+            .collect();
+        new_points.sort_unstable();
+        new_points.dedup();
+        let code = self.code;
+        let scanned: Vec<_> = new_points
+            .par_iter()
+            .map(|&(x, y)| ((x, y), scan_point(x, y, code)))
+            .collect();
+        self.stats.intcode_runs += scanned.len();
+        self.stats.cache_hits += points.len() - scanned.len();
+        self.cache.extend(scanned);
+        points.iter().map(|p| self.cache[p]).collect()
+    }
+}
+
+#[aoc(day19, part1)]
+fn scan_points(scanner_code: &[i64]) -> usize {
+    let points: Vec<_> = (0..50).flat_map(|x| (0..50).map(move |y| (x, y))).collect();
+    Scanner::new(scanner_code)
+        .scan_many(&points)
+        .into_iter()
+        .filter(|&pulled| pulled)
+        .count()
+}
+
+/// Top left corner of the closest `width`x`height` rectangle fitting in the beam. Only the top
+/// right and bottom left corners need to be checked as both edges move right going down.
+pub fn find_rectangle(
+    width: usize,
+    height: usize,
+    scanner: &mut Scanner,
+) -> Result<Point<usize>, String> {
+    if width == 0 || height == 0 {
+        return Err("The rectangle must not be empty".to_owned());
+    }
+    let mut tracker = EdgeTracker::new();
+    // right edges of the last `height` rows, `None` where the beam missed the row
+    let mut rights = VecDeque::with_capacity(height);
+    for y in 0..MAX_ROWS {
+        let edges = tracker.edges(y, scanner)?;
+        if rights.len() == height {
+            rights.pop_front();
+        }
+        rights.push_back(edges.map(|(_, right)| right));
+        if let (Some(&Some(top_right)), Some((left, _))) = (rights.front(), edges) {
+            if rights.len() == height && top_right + 1 >= left + width {
+                return Ok(Point::new(left, y + 1 - height));
+            }
+        }
+    }
+    Err(format!(
+        "No {}x{} rectangle in the first {} rows",
+        width, height, MAX_ROWS
+    ))
+}
+
+#[aoc(day19, part2)]
+fn find_distance(scanner_code: &[i64]) -> Result<usize, String> {
+    let mut scanner = Scanner::new(scanner_code);
+    let corner = find_rectangle(100, 100, &mut scanner)?;
+    Ok(10000 * corner.x + corner.y)
+}
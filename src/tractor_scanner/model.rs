@@ -0,0 +1,180 @@
+use super::edges::{right_edge_from, scan_row};
+use super::Scanner;
+use crate::grid::Point;
+use crate::intcode::parse_intcode;
+
+// Rows scanned from column 0 to get a first estimate of the edges
+const FIRST_ROWS: [usize; 3] = [10, 20, 40];
+// Furthest from a predicted edge the real one is looked for
+const SEARCH_WINDOW: usize = 200;
+// Rows checked above the one found by the binary search, as rounding makes the edges wobble
+const WOBBLE: usize = 5;
+// Times the search range is widened before giving up on the beam
+const MAX_EXPANSIONS: usize = 32;
+
+/// Beam edges modelled as lines leaving the emitter, `x = slope * y`, fitted on sampled rows
+#[derive(Debug, Clone)]
+pub struct BeamModel {
+    // (row, left edge, right edge)
+    samples: Vec<(usize, usize, usize)>,
+    left: f64,
+    right: f64,
+}
+
+// Closest column to `guess` in the beam, not too far away
+fn nearest_inside(y: usize, guess: usize, scanner: &mut Scanner) -> Option<usize> {
+    for d in 0..=SEARCH_WINDOW {
+        if scanner.scan(guess + d, y) {
+            return Some(guess + d);
+        }
+        if d > 0 && d <= guess && scanner.scan(guess - d, y) {
+            return Some(guess - d);
+        }
+    }
+    None
+}
+
+// Least squares slope of a line going through the emitter
+fn fit_slope(points: impl Iterator<Item = (usize, usize)>) -> f64 {
+    let (xy, yy) = points.fold((0.0, 0.0), |(xy, yy), (y, x)| {
+        let (x, y) = (x as f64, y as f64);
+        (xy + x * y, yy + y * y)
+    });
+    xy / yy
+}
+
+impl BeamModel {
+    /// Fits the edges on a few rows near the emitter, scanned completely
+    pub fn sample(scanner: &mut Scanner) -> Result<Self, String> {
+        let mut samples = Vec::new();
+        for &y in &FIRST_ROWS {
+            if let Some((left, right)) = scan_row(y, scanner)? {
+                samples.push((y, left, right));
+            }
+        }
+        if samples.is_empty() {
+            return Err(format!("No beam in rows {:?}", FIRST_ROWS));
+        }
+        Ok(Self::from_samples(samples))
+    }
+    fn from_samples(samples: Vec<(usize, usize, usize)>) -> Self {
+        BeamModel {
+            left: fit_slope(samples.iter().map(|&(y, left, _)| (y, left))),
+            right: fit_slope(samples.iter().map(|&(y, _, right)| (y, right))),
+            samples,
+        }
+    }
+    /// Columns per row of the left and right edges
+    pub fn slopes(&self) -> (f64, f64) {
+        (self.left, self.right)
+    }
+    pub fn predict(&self, y: usize) -> (usize, usize) {
+        let y = y as f64;
+        (
+            (self.left * y).round() as usize,
+            (self.right * y).round() as usize,
+        )
+    }
+
+    /// Exact left edge of row `y`, looked for around the predicted one
+    pub fn left_edge(&self, y: usize, scanner: &mut Scanner) -> Option<usize> {
+        let mut x = nearest_inside(y, self.predict(y).0, scanner)?;
+        while x > 0 && scanner.scan(x - 1, y) {
+            x -= 1;
+        }
+        Some(x)
+    }
+    /// Exact right edge of row `y`, looked for around the predicted one
+    pub fn right_edge(&self, y: usize, scanner: &mut Scanner) -> Option<usize> {
+        let x = nearest_inside(y, self.predict(y).1, scanner)?;
+        right_edge_from(x, y, scanner).ok()
+    }
+    /// Adds the exact edges of row `y` to the samples and fits the edges again
+    pub fn refine(&mut self, y: usize, scanner: &mut Scanner) -> Result<(), String> {
+        let left = self.left_edge(y, scanner);
+        let right = self.right_edge(y, scanner);
+        match (left, right) {
+            (Some(left), Some(right)) => {
+                let mut samples = std::mem::take(&mut self.samples);
+                samples.push((y, left, right));
+                *self = Self::from_samples(samples);
+                Ok(())
+            }
+            _ => Err(format!("Lost the beam around row {}", y)),
+        }
+    }
+
+    /// First row the model expects the top of a `size` square to fit on
+    pub fn predict_square_row(&self, size: usize) -> Option<usize> {
+        if self.right <= self.left {
+            return None;
+        }
+        let side = (size - 1) as f64;
+        Some((side * (1.0 + self.left) / (self.right - self.left)).ceil() as usize)
+    }
+    /// Column of a `size` square whose top is on row `y`, if it fits in the beam, checking the
+    /// same two corners as `find_rectangle`
+    fn square_on_row(&self, y: usize, size: usize, scanner: &mut Scanner) -> Option<usize> {
+        let right = self.right_edge(y, scanner)?;
+        let left = self.left_edge(y + size - 1, scanner)?;
+        if right + 1 >= left + size {
+            Some(left)
+        } else {
+            None
+        }
+    }
+}
+
+/// Top left corner of the `size` square closest to the emitter, found by a binary search
+/// on the rows around the one predicted by the model
+pub fn find_square(size: usize, scanner: &mut Scanner) -> Result<Point<usize>, String> {
+    if size == 0 {
+        return Err("The square must not be empty".to_owned());
+    }
+    let mut model = BeamModel::sample(scanner)?;
+    let guess = model
+        .predict_square_row(size)
+        .ok_or_else(|| "The beam does not get wider".to_owned())?;
+    if guess > FIRST_ROWS[FIRST_ROWS.len() - 1] {
+        model.refine(guess, scanner)?;
+    }
+    let guess = model
+        .predict_square_row(size)
+        .ok_or_else(|| "The beam does not get wider".to_owned())?;
+
+    let mut high = guess.max(1);
+    let mut step = (high / 16).max(1);
+    let mut expansions = 0;
+    while model.square_on_row(high, size, scanner).is_none() {
+        expansions += 1;
+        if expansions > MAX_EXPANSIONS {
+            return Err(format!("No {0}x{0} square fits in the beam", size));
+        }
+        high += step;
+        step *= 2;
+    }
+    let mut low = high / 2;
+    while low > 0 && model.square_on_row(low, size, scanner).is_some() {
+        low /= 2;
+    }
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if model.square_on_row(middle, size, scanner).is_some() {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    let y = (high.saturating_sub(WOBBLE)..high)
+        .find(|&y| model.square_on_row(y, size, scanner).is_some())
+        .unwrap_or(high);
+    let x = model.square_on_row(y, size, scanner).unwrap();
+    Ok(Point::new(x, y))
+}
+
+#[aoc(day19, part2, model)]
+fn find_distance_model(scanner_code: &[i64]) -> Result<usize, String> {
+    let mut scanner = Scanner::new(scanner_code);
+    let corner = find_square(100, &mut scanner)?;
+    Ok(10000 * corner.x + corner.y)
+}
@@ -0,0 +1,127 @@
+use super::Scanner;
+use crate::bitmap::{Raster, Rgb, BLUE, YELLOW};
+use crate::grid::{BoundingBox, Grid, Point, SparseGrid};
+use crate::render::{Cell, Renderer};
+use std::io::{self, Write};
+
+const BACKGROUND: Rgb = [20, 20, 30];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pixel {
+    Empty,
+    Beam,
+    Rectangle,
+}
+
+impl Pixel {
+    fn color(self) -> Rgb {
+        match self {
+            Pixel::Empty => BACKGROUND,
+            Pixel::Beam => BLUE,
+            Pixel::Rectangle => YELLOW,
+        }
+    }
+}
+
+/// The beam around a rectangle, as seen by the drones
+#[derive(Debug, Clone)]
+pub struct BeamView {
+    // top left corner of the area
+    origin: Point<usize>,
+    pixels: Grid<Pixel>,
+}
+
+impl BeamView {
+    /// Scans the `width`x`height` rectangle at `corner` and `margin` points around it
+    pub fn scan(
+        scanner: &mut Scanner,
+        corner: Point<usize>,
+        width: usize,
+        height: usize,
+        margin: usize,
+    ) -> Self {
+        let origin = Point::new(
+            corner.x.saturating_sub(margin),
+            corner.y.saturating_sub(margin),
+        );
+        let end = Point::new(corner.x + width + margin, corner.y + height + margin);
+        let points: Vec<_> = (origin.y..end.y)
+            .flat_map(|y| (origin.x..end.x).map(move |x| (x, y)))
+            .collect();
+        let pulled = scanner.scan_many(&points);
+        let rectangle = BoundingBox {
+            min: corner,
+            max: Point::new(
+                corner.x + width.saturating_sub(1),
+                corner.y + height.saturating_sub(1),
+            ),
+        };
+        let rows = pulled
+            .chunks((end.x - origin.x).max(1))
+            .zip(origin.y..)
+            .map(|(row, y)| {
+                row.iter().zip(origin.x..).map(move |(&pulled, x)| {
+                    if width > 0 && height > 0 && rectangle.contains(&Point::new(x, y)) {
+                        Pixel::Rectangle
+                    } else if pulled {
+                        Pixel::Beam
+                    } else {
+                        Pixel::Empty
+                    }
+                })
+            });
+        BeamView {
+            origin,
+            pixels: Grid::from_rows(rows).unwrap(),
+        }
+    }
+
+    /// Terminal drawing with the rectangle standing out
+    pub fn render(&self) -> String {
+        let beam = Cell::new(' ').plain('#').bg(yansi::Color::Blue);
+        let rectangle = Cell::new(' ').plain('O').bg(yansi::Color::Yellow);
+        let cells: SparseGrid<Cell> = self
+            .pixels
+            .iter()
+            .filter_map(|(p, pixel)| {
+                let cell = match pixel {
+                    Pixel::Empty => return None,
+                    Pixel::Beam => beam,
+                    Pixel::Rectangle => rectangle,
+                };
+                let x = (self.origin.x + p.x) as isize;
+                let y = (self.origin.y + p.y) as isize;
+                Some((Point::new(x, y), cell))
+            })
+            .collect();
+        let min = Point::new(self.origin.x as isize, self.origin.y as isize);
+        let bounds = BoundingBox {
+            min,
+            max: Point::new(
+                min.x + self.pixels.width() as isize - 1,
+                min.y + self.pixels.height() as isize - 1,
+            ),
+        };
+        Renderer::new()
+            .bounds(bounds)
+            .background(Cell::new('.'))
+            .legend(beam, "beam")
+            .legend(rectangle, "rectangle")
+            .render(&cells)
+    }
+
+    pub fn raster(&self) -> Raster {
+        let mut raster = Raster::new(self.pixels.width(), self.pixels.height(), BACKGROUND);
+        for (p, pixel) in self.pixels.iter() {
+            raster.set(p.x, p.y, pixel.color());
+        }
+        raster
+    }
+    /// Binary PPM, each point being a `scale`x`scale` square
+    pub fn write_ppm<W: Write>(&self, out: &mut W, scale: usize) -> io::Result<()> {
+        self.raster().write_ppm(out, scale)
+    }
+    pub fn write_png<W: Write>(&self, out: W, scale: usize) -> io::Result<()> {
+        self.raster().write_png(out, scale)
+    }
+}